@@ -0,0 +1,71 @@
+//! Generates the list of solution modules linked into the `aggregate` binary.
+//! Every `src/bin/NN.rs` and `src/bin/YYYY-NN.rs` file is included as a module, so all days can run in a single
+//! process. The year and day a solution registers with must match its file name, a mismatch fails the build.
+use std::{env, fs, path::Path};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    let mut days: Vec<(Option<u16>, u8, String)> = fs::read_dir(&bin_dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "rs" {
+                return None;
+            }
+            let stem = path.file_stem()?.to_str()?;
+            let (year, day) = parse_stem(stem)?;
+            Some((year, day, path.to_str().unwrap().to_string()))
+        })
+        .collect();
+    days.sort_unstable();
+
+    let mut modules = String::new();
+    let mut entries = String::new();
+
+    for (year, day, path) in &days {
+        let (name, expected_year, declaration) = match year {
+            Some(year) => (
+                format!("day_{year}_{day:02}"),
+                year.to_string(),
+                format!("`year = {year}`"),
+            ),
+            None => (
+                format!("day_{day:02}"),
+                "advent_of_code::template::Year::PRIMARY.into_inner()".to_string(),
+                "the primary year".to_string(),
+            ),
+        };
+        modules.push_str(&format!(
+            "#[path = {path:?}]\n#[allow(dead_code, unused_imports)]\nmod {name};\n"
+        ));
+        // NOTE: the macro declares the year, so a `YYYY-NN.rs` without it would register as the primary year.
+        let message = format!("{path} must declare {declaration} and day {day} in `solution!`");
+        modules.push_str(&format!(
+            "const _: () = assert!(\n    {name}::SOLUTION.year.into_inner() == {expected_year}\n        && {name}::SOLUTION.day.into_inner() == {day},\n    {message:?}\n);\n"
+        ));
+        entries.push_str(&format!("    {name}::SOLUTION,\n"));
+    }
+
+    let out = format!(
+        "{modules}\n/// Every scaffolded solution, ordered by year and day.\n\
+         pub const SOLUTIONS: &[advent_of_code::template::solution::Registration] = &[\n{entries}];\n"
+    );
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out_path, out).unwrap();
+}
+
+/// Parses `NN` and `YYYY-NN` file stems. Other binaries, like `aggregate`, are skipped.
+fn parse_stem(stem: &str) -> Option<(Option<u16>, u8)> {
+    let (year, day) = match stem.split_once('-') {
+        Some((year, day)) if year.len() == 4 => (Some(year.parse().ok()?), day),
+        Some(_) => return None,
+        None => (None, stem),
+    };
+    let is_day = day.len() == 2 && day.bytes().all(|b| b.is_ascii_digit());
+    let day = day.parse::<u8>().ok()?;
+    (is_day && (1..=25).contains(&day)).then_some((year, day))
+}
@@ -4,7 +4,7 @@ use itertools::Itertools;
 use num_integer::Integer;
 use rayon::prelude::*;
 
-advent_of_code::solution!(8);
+advent_of_code::solution!(8, variants = [crt: 2 => part_three]);
 
 pub fn part_one(input: &str) -> Option<u64> {
     let map = parse(input)?;
@@ -24,80 +24,82 @@ pub fn part_two(input: &str) -> Option<u64> {
     )
 }
 
+/// Most pairs of destinations [`part_three`] combines for a single ghost, the number of destinations can grow with
+/// the product of the destinations of every ghost.
+const MAX_DESTINATION_PAIRS: usize = 1_000_000;
+
+/// General solution of part two, which doesn't assume that every cycle starts at its destination.
 pub fn part_three(input: &str) -> Option<u64> {
     let map = parse(input)?;
 
     // Find the cycles corresponding to each starting node.
-    // Each cycle involves some starting offset S until a XXZ node is first reached
-    // and then some cycle length L until the XXZ node is reached again.
-    // If any starting node cycles before reaching a destination, then cycle_offset_and_length
-    // will return none.
-    // Note that XXZ might not be the first destination reached, as we need a destination that is
-    // part of the cycle.
-    let mut cycles = map
+    // Each cycle involves some starting offset S until the cycle is entered and then some cycle length L.
+    // Every XXZ node within the cycle is a destination that is first reached after D >= S steps,
+    // and then again every L steps.
+    // If any starting node cycles without reaching a destination, then cycle_destinations will return none.
+    // NOTE: destinations reached before entering the cycle are never reached again, so they are ignored.
+    let cycles = map
         .nodes
         .par_iter()
         .copied()
         .filter(Node::ends_with_a)
-        .map(|start| map.cycle_offset_and_length(start, Node::ends_with_z))
-        // NOTE: if any of the cycles don't reach the destination, we can't find a solution,
-        // so we return None.
+        .map(|start| map.cycle_destinations(start, Node::ends_with_z))
         .collect::<Option<Vec<_>>>()?;
 
-    // Iteratively combine cycles by finding when they first intersect and their period.
-    // This can be done by solving the equation A + Bx = C + Dy for integer solutions,
-    // which can be rewritten as the linear Diophantine equation Ax + By = C
-    while let Some(last_cycle) = cycles.pop() {
-        let Some(second_last_cycle) = cycles.last_mut() else {
-            // We are at the last remaining cycle, so we are done!
-            return Some(last_cycle.0);
-        };
-
-        // Use extended GCD/Euclids algorithm to find solutions to ax + by = c
-        let a = last_cycle.1 as i64;
-        let b = -(second_last_cycle.1 as i64);
-        let c = second_last_cycle.0 as i64 - last_cycle.0 as i64;
-        let gcd = a.extended_gcd(&b);
-        if c % gcd.gcd != 0 {
-            // No solutions exist.
+    // Iteratively combine cycles by finding when any of their destinations intersect and their period.
+    // This is done for each pair of destinations, see combine_destinations.
+    // NOTE: if no starting nodes exist, there's no solution.
+    let mut cycles = cycles.into_iter();
+    let mut destinations = cycles.next()?;
+    for second in cycles {
+        // Too many pairs would take too long, skip the input like other variants that don't fit an input.
+        if destinations.len() * second.len() > MAX_DESTINATION_PAIRS {
             return None;
         }
-        let mut x = gcd.x * (c / gcd.gcd);
-        let y = gcd.y * (c / gcd.gcd);
-        let x_period = -b / gcd.gcd;
-        let y_period = a / gcd.gcd;
-
-        // We need to find the smallest positive solution, so we adjust the solution found by the euclid
-        // algorithm accordingly.
-        // If we don't do this, the solution can explode in size (and likely miss the actual smallest solution at the end).
-        let x_k = if x < 0 {
-            (-x + x_period - 1) / x_period
-        } else {
-            0
-        };
-        let y_k = if y < 0 {
-            (-y + y_period - 1) / y_period
-        } else {
-            0
-        };
-        let k = x_k.max(y_k);
-        x += k * x_period;
-        x %= x_period;
-        if x == 0 {
-            x = x_period;
-        }
+        destinations = destinations
+            .iter()
+            .cartesian_product(&second)
+            .filter_map(|(&first, &second)| combine_destinations(first, second))
+            .collect();
+        // All combined destinations repeat with the same period, so of those that are reached at the same point of the
+        // period, only the first one matters.
+        destinations.sort_unstable_by_key(|&(offset, length)| (offset % length, offset));
+        destinations.dedup_by_key(|&mut (offset, length)| offset % length);
+    }
+
+    destinations.into_iter().map(|(offset, _)| offset).min()
+}
 
-        // Now that we have a solution to the pair, we can plug in x to find the smallest
-        // positive value that is the result of Z = A + Bx = C + Dy, which is the new offset,
-        // and then the periodicity of the solution, which is the LCM(B, D).
-        let x = x as u64;
-        let new_offset = last_cycle.0 + last_cycle.1 * x;
-        let new_cycle_length = ((a * b) / gcd.gcd).unsigned_abs();
-        *second_last_cycle = (new_offset, new_cycle_length);
+/// Combines two destinations `(D, L)` that are reached after `D + Lx` steps into the destination of both, if any.
+fn combine_destinations(
+    (first_offset, first_length): (u64, u64),
+    (second_offset, second_length): (u64, u64),
+) -> Option<(u64, u64)> {
+    // Both are reached when A + Bx = C + Dy, which can be rewritten as the linear Diophantine equation
+    // Bx - Dy = C - A. Use extended GCD/Euclids algorithm to find solutions to it.
+    let a = i128::from(first_offset);
+    let b = i128::from(first_length);
+    let c = i128::from(second_offset);
+    let d = i128::from(second_length);
+
+    let gcd = b.extended_gcd(&d);
+    if (c - a) % gcd.gcd != 0 {
+        // No solutions exist.
+        return None;
     }
 
-    // This will only be hit if there were no starting nodes at all.
-    None
+    // Find the smallest non-negative x, then move the solution after the first time both destinations are reached.
+    // The solutions repeat every LCM(B, D) steps.
+    let x_period = d / gcd.gcd;
+    let x = (gcd.x * ((c - a) / gcd.gcd)).rem_euclid(x_period);
+    let length = b * x_period;
+    let mut offset = a + b * x;
+    let first_reached = a.max(c);
+    if offset < first_reached {
+        offset += (first_reached - offset + length - 1) / length * length;
+    }
+
+    Some((u64::try_from(offset).ok()?, u64::try_from(length).ok()?))
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -142,11 +144,13 @@ impl Map {
         unreachable!()
     }
 
-    pub fn cycle_offset_and_length<F: Fn(&Node) -> bool>(
+    /// Every destination within the cycle that `start` ends up in, as the number of steps `D` until it is first reached
+    /// and the cycle length `L` after which it is reached again.
+    pub fn cycle_destinations<F: Fn(&Node) -> bool>(
         &self,
         start: Node,
         is_dest: F,
-    ) -> Option<(u64, u64)> {
+    ) -> Option<Vec<(u64, u64)>> {
         let mut seen = HashMap::with_capacity(self.nodes.len() * self.instructions.len());
         seen.extend(
             self.nodes
@@ -180,19 +184,20 @@ impl Map {
         };
 
         // We found a cycle starting at cur. We calculated the cycle length
-        // inside the loop, so now we just need to find how far into the cycle
-        // the first destination node is and add that to the cycle offset.
-        let mut cycle_dest_offset = 0;
-        while !is_dest(&cur) {
+        // inside the loop, so now we just need to walk it once to find every
+        // destination and how far into the cycle it is.
+        // The cycle was first entered cycle_length steps ago.
+        let cycle_start = offset_steps - cycle_length;
+        let mut destinations = vec![];
+        for cycle_dest_offset in 0..cycle_length {
+            if is_dest(&cur) {
+                destinations.push((cycle_start + cycle_dest_offset, cycle_length));
+            }
             let (_, direction) = instructions.next()?;
             cur = self.next(cur, direction);
-            cycle_dest_offset += 1;
         }
 
-        Some((
-            offset_steps - (cycle_length - cycle_dest_offset),
-            cycle_length,
-        ))
+        (!destinations.is_empty()).then_some(destinations)
     }
 }
 
@@ -285,4 +290,12 @@ mod tests {
         ));
         assert_eq!(result, Some(6));
     }
+
+    #[test]
+    fn test_part_three() {
+        let result = part_three(&advent_of_code::template::read_file_part(
+            "examples", DAY, 2,
+        ));
+        assert_eq!(result, Some(6));
+    }
 }
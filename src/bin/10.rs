@@ -1,24 +1,28 @@
 use std::str::FromStr;
 
+use advent_of_code::template::solution::PartResult;
 use either::Either;
 use tinyvec::ArrayVec;
 
 advent_of_code::solution!(10);
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let field = input.parse::<Field>().expect("valid input");
-    field.find_loop_length()
+pub fn part_one(input: &str) -> PartResult<u32> {
+    let field = input.parse::<Field>()?;
+    Ok(field.find_loop_length().ok_or("no loop found")?)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut field = input.parse::<Field>().expect("valid input");
+pub fn part_two(input: &str) -> PartResult<u32> {
+    let mut field = input.parse::<Field>()?;
     field.remove_non_loop_pipes();
 
     let mut inside_count = 0;
     for row in 0..field.rows {
         let mut inside = false;
         for col in 0..field.cols {
-            match field.get(Position { row, col })? {
+            match field
+                .get(Position { row, col })
+                .ok_or("position outside of the field")?
+            {
                 Tile::VerticalPipe | Tile::SouthEastPipe | Tile::SouthWestPipe => inside = !inside,
                 Tile::Ground if inside => inside_count += 1,
                 _ => {}
@@ -26,7 +30,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         }
     }
 
-    Some(inside_count)
+    Ok(inside_count)
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -352,7 +356,7 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4));
+        assert_eq!(result.unwrap(), 4);
     }
 
     #[test]
@@ -360,18 +364,18 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file_part(
             "examples", DAY, 2,
         ));
-        assert_eq!(result, Some(4));
+        assert_eq!(result.unwrap(), 4);
         let result = part_two(&advent_of_code::template::read_file_part(
             "examples", DAY, 3,
         ));
-        assert_eq!(result, Some(4));
+        assert_eq!(result.unwrap(), 4);
         let result = part_two(&advent_of_code::template::read_file_part(
             "examples", DAY, 4,
         ));
-        assert_eq!(result, Some(8));
+        assert_eq!(result.unwrap(), 8);
         let result = part_two(&advent_of_code::template::read_file_part(
             "examples", DAY, 5,
         ));
-        assert_eq!(result, Some(10));
+        assert_eq!(result.unwrap(), 10);
     }
 }
@@ -1,32 +1,55 @@
 use rayon::{iter::ParallelIterator, str::ParallelString};
 use tinyvec::ArrayVec;
 
-advent_of_code::solution!(12);
+advent_of_code::solution!(12, variants = [brute_force: 1 => part_one_brute_force]);
 
 pub fn part_one(input: &str) -> Option<u64> {
-    Some(input.par_lines().map(|line| solve_line(line, 1)).sum())
+    Some(
+        input
+            .par_lines()
+            .map(|line| solve_line(line, 1, solve_dp))
+            .sum(),
+    )
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    Some(input.par_lines().map(|line| solve_line(line, 5)).sum())
+    Some(
+        input
+            .par_lines()
+            .map(|line| solve_line(line, 5, solve_dp))
+            .sum(),
+    )
+}
+
+/// Tries every arrangement of the springs, which is only feasible without unfolding them.
+pub fn part_one_brute_force(input: &str) -> Option<u64> {
+    Some(
+        input
+            .par_lines()
+            .map(|line| {
+                solve_line(line, 1, |spring_records, damaged_counts| {
+                    solve_brute_force_recursion(spring_records, damaged_counts, 0)
+                })
+            })
+            .sum(),
+    )
 }
 
-fn solve_line(line: &str, copies: usize) -> u64 {
+fn solve_line(line: &str, copies: usize, solve: fn(&[u8], &[u32]) -> u64) -> u64 {
     let (spring_records, damaged_counts) = line.split_once(' ').expect("should be valid input");
     let spring_records = vec![spring_records; copies].join("?");
     let spring_records = spring_records.as_bytes();
 
-    let damaged_counts = std::iter::repeat(
+    let damaged_counts = std::iter::repeat_n(
         damaged_counts
             .split(',')
             .map(|s| s.parse::<u32>().expect("should be valid spring count")),
+        copies,
     )
-    .take(copies)
     .flatten()
     .collect::<ArrayVec<[u32; 64]>>();
 
-    // solve_brute_force_recursion(spring_records, &damaged_counts, 0)
-    solve_dp(spring_records, &damaged_counts)
+    solve(spring_records, &damaged_counts)
 }
 
 fn solve_dp(spring_records: &[u8], damaged_counts: &[u32]) -> u64 {
@@ -99,7 +122,6 @@ fn solve_dp(spring_records: &[u8], damaged_counts: &[u32]) -> u64 {
     prev[spring_records.len()]
 }
 
-#[allow(unused)]
 fn solve_brute_force_recursion(
     spring_records: &[u8],
     damaged_counts: &[u32],
@@ -3,6 +3,8 @@ use std::{
     fmt::{Display, Write},
 };
 
+use advent_of_code::template::params;
+
 advent_of_code::solution!(14);
 
 pub fn part_one(input: &str) -> Option<u32> {
@@ -29,17 +31,19 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
+    let cycles: u64 = params::get("cycles", 1_000_000_000);
     let mut platform = Platform::parse(input);
     let mut count = 0u64;
     let mut seen = HashMap::new();
-    loop {
+    // NOTE: stops after `cycles` if they end before the platform repeats itself.
+    while count < cycles {
         let key = platform.cycle();
         count += 1;
         match seen.entry(key) {
             Entry::Occupied(val) => {
                 let cycle_length = count - *val.get();
-                let remainder = 1_000_000_000
-                    - (((1_000_000_000 - count) as f64 / cycle_length as f64).floor() as u64
+                let remainder = cycles
+                    - (((cycles - count) as f64 / cycle_length as f64).floor() as u64
                         * cycle_length
                         + count);
                 for _ in 0..remainder {
@@ -177,12 +181,6 @@ struct CachedDistance {
     east: u8,
 }
 
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
-struct RockStack {
-    iteration: u16,
-    count: u8,
-}
-
 impl Coordinate {
     const fn new(row: u8, col: u8) -> Self {
         Self { row, col }
@@ -250,6 +248,7 @@ impl Display for Platform {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::params::Params;
 
     #[test]
     fn test_part_one() {
@@ -262,4 +261,16 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(64));
     }
+
+    #[test]
+    fn test_part_two_few_cycles() {
+        let input = &advent_of_code::template::read_file("examples", DAY);
+        for (cycles, load) in [(0, 104), (1, 87), (2, 69), (3, 69)] {
+            let result = params::with(
+                &Params::from([("cycles", cycles.to_string().as_str())]),
+                || part_two(input),
+            );
+            assert_eq!(result, Some(load), "{cycles} cycles");
+        }
+    }
 }
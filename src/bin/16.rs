@@ -207,12 +207,12 @@ impl Debug for Tile {
     }
 }
 
-#[macro_export]
 macro_rules! coord {
     ($x:expr, $y:expr) => {
         Coordinate::new($x, $y)
     };
 }
+use coord;
 
 #[cfg(test)]
 mod tests {
@@ -2,23 +2,26 @@ use std::fmt::{Display, Write};
 
 use enum_ordinalize::Ordinalize;
 
-advent_of_code::solution!(17);
+advent_of_code::solution!(17, parse -> Map);
 
-pub fn part_one(input: &str) -> Option<u16> {
-    solve_a_star(input, 1, 3)
+pub fn parse(input: &str) -> Map {
+    Map::parse(input)
 }
 
-pub fn part_two(input: &str) -> Option<u16> {
-    solve_a_star(input, 4, 10)
+pub fn part_one(map: &Map) -> Option<u16> {
+    solve_a_star(map, 1, 3)
+}
+
+pub fn part_two(map: &Map) -> Option<u16> {
+    solve_a_star(map, 4, 10)
 }
 
 fn solve_a_star(
-    input: &str,
+    map: &Map,
     min_straight_distance: usize,
     max_straight_distance: usize,
 ) -> Option<u16> {
     use Alignment::{Horizontal, Vertical};
-    let map = Map::parse(input);
     let start = Coordinate::new(0, 0);
     let goal = Coordinate::new(map.height - 1, map.width - 1);
     let mut queue = BucketQueue::new();
@@ -78,7 +81,7 @@ fn solve_a_star(
 }
 
 #[derive(Debug, Clone)]
-struct Map {
+pub struct Map {
     costs: Vec<u16>,
     width: usize,
     height: usize,
@@ -120,7 +123,7 @@ impl Map {
         }
     }
 
-    pub fn precalculate_heuristic(
+    fn precalculate_heuristic(
         &self,
         goal: Coordinate,
         min_straight_distance: usize,
@@ -188,9 +191,7 @@ impl<T: Copy> BucketQueue<T> {
     }
 
     pub fn pop(&mut self) -> Option<(usize, T)> {
-        let Some(min_cost) = self.first_non_empty else {
-            return None;
-        };
+        let min_cost = self.first_non_empty?;
         let value = self.buckets[min_cost].pop().unwrap();
         if self.buckets[min_cost].is_empty() {
             self.first_non_empty =
@@ -200,12 +201,12 @@ impl<T: Copy> BucketQueue<T> {
     }
 }
 
-#[macro_export]
 macro_rules! coord {
     ($x:expr, $y:expr) => {
         Coordinate::new($x, $y)
     };
 }
+use coord;
 
 #[cfg(test)]
 mod tests {
@@ -213,13 +214,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(102));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(94));
         let input = r#"
 111111111111
@@ -228,7 +233,7 @@ mod tests {
 999999999991
 999999999991"#
             .trim();
-        let result = part_two(input);
+        let result = part_two(&parse(input));
         assert_eq!(result, Some(71));
     }
 }
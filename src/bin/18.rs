@@ -1,4 +1,7 @@
-advent_of_code::solution!(18);
+advent_of_code::solution!(
+    18,
+    variants = [generic: 1 => part_one_generic, generic: 2 => part_two_generic]
+);
 
 pub fn part_one(input: &str) -> Option<i64> {
     Some(solve_part1_fast(input))
@@ -8,6 +11,15 @@ pub fn part_two(input: &str) -> Option<i64> {
     Some(solve_part2_fast(input))
 }
 
+/// Parses every line before walking the trench, instead of walking it while scanning the input.
+pub fn part_one_generic(input: &str) -> Option<i64> {
+    Some(solve(input, parse_part1))
+}
+
+pub fn part_two_generic(input: &str) -> Option<i64> {
+    Some(solve(input, parse_part2))
+}
+
 fn solve_part1_fast(input: &str) -> i64 {
     let input = input.as_bytes();
     let mut border_points = 0;
@@ -80,7 +92,6 @@ fn solve_part2_fast(input: &str) -> i64 {
     area + border_points / 2 + 1
 }
 
-#[allow(unused)]
 fn solve<F: Fn(&[u8]) -> (Direction, i64)>(input: &str, parser: F) -> i64 {
     let mut border_points = 0;
     let mut area = 0;
@@ -100,7 +111,6 @@ fn solve<F: Fn(&[u8]) -> (Direction, i64)>(input: &str, parser: F) -> i64 {
     area + border_points / 2 + 1
 }
 
-#[allow(unused)]
 fn parse_part1(line: &[u8]) -> (Direction, i64) {
     let dir: Direction = line[0].try_into().expect("valid direction");
     let distance = line[2..]
@@ -111,7 +121,6 @@ fn parse_part1(line: &[u8]) -> (Direction, i64) {
     (dir, distance)
 }
 
-#[allow(unused)]
 fn parse_part2(line: &[u8]) -> (Direction, i64) {
     let hex = &line[line.len() - 7..line.len() - 1];
     let dir: Direction = hex[5].try_into().expect("valid direction");
@@ -154,7 +163,8 @@ impl Point {
     }
 }
 
-#[derive(Debug)]
+#[derive(thiserror::Error, Debug)]
+#[error("{0} is not a valid direction")]
 struct InvalidDirectionError(char);
 
 impl TryFrom<u8> for Direction {
@@ -1,5 +1,7 @@
 use std::collections::{HashMap, VecDeque};
 
+use advent_of_code::template::params;
+
 advent_of_code::solution!(20);
 
 pub fn part_one(input: &str) -> Option<u64> {
@@ -7,7 +9,7 @@ pub fn part_one(input: &str) -> Option<u64> {
     let mut low_pulses = 0;
     let mut high_pulses = 0;
     let mut queue = VecDeque::with_capacity(64);
-    for _ in 0..1000 {
+    for _ in 0..params::get("presses", 1000) {
         queue.push_back((network.broadcaster_id, false, network.broadcaster_id));
         while let Some((id, pulse, from)) = queue.pop_front() {
             if pulse {
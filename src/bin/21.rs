@@ -3,12 +3,16 @@ use std::{
     fmt::{Display, Write},
 };
 
+use advent_of_code::template::params;
 use itertools::Itertools;
 
-advent_of_code::solution!(21);
+advent_of_code::solution!(
+    21,
+    variants = [brute_force: 1 => part_one_brute_force, brute_force: 2 => part_two_brute_force]
+);
 
 pub fn part_one(input: &str) -> Option<u32> {
-    Some(reachable_in_steps(input, 64))
+    Some(reachable_in_steps(input, params::get("steps", 64)))
 }
 
 fn reachable_in_steps(input: &str, steps: usize) -> u32 {
@@ -110,16 +114,14 @@ pub fn part_two(input: &str) -> Option<usize> {
         })
         .count();
 
-    // The step count is side length/2 + (side length * k)
+    // The step count is side length/2 + (side length * k), i.e. 65 + 131k for the real input.
     // k is how many grids of tiles we can travel before reaching the outer edge.
-    const DESIRED_STEPS: usize = 26501365;
-    let k = (DESIRED_STEPS - half_width) / map.width;
-    debug_assert_eq!(
-        DESIRED_STEPS,
-        k * map.width + map.width / 2,
-        "step count ({}) should be 65 + 131k",
-        DESIRED_STEPS
-    );
+    // Other step counts don't end on the edge of a grid, so this approach has no answer for them.
+    let desired_steps: usize = params::get("infinite_steps", 26501365);
+    let k = desired_steps
+        .checked_sub(half_width)
+        .filter(|steps| steps % map.width == 0)?
+        / map.width;
 
     // We will end up with (k + 1)^2 odd grids, and k^2 even grids.
     // But we need to add in the missing corners around the edge, and
@@ -173,9 +175,23 @@ fn distance_from_points(
         .map(move |(coord, index)| (coord, distance_from_start[index]))
 }
 
-// Note: this is unused because it only works for a step count of a few thousand.
-// I was mainly using it to sanity check my answer.
-#[allow(unused)]
+/// Walks the infinitely repeating map, which gives the same answer as long as the steps don't leave the first map.
+pub fn part_one_brute_force(input: &str) -> Option<u32> {
+    Some(part2_brute_force(input, params::get("steps", 64)))
+}
+
+/// Most steps the brute force walks, it takes a few seconds for 5000 steps on the real input.
+const MAX_BRUTE_FORCE_STEPS: usize = 5000;
+
+/// Walks the infinitely repeating map, which is only feasible for a step count of a few thousand.
+/// Returns `None` for more steps, like the real input needs.
+pub fn part_two_brute_force(input: &str) -> Option<usize> {
+    let steps = params::get("infinite_steps", 26501365);
+    (steps <= MAX_BRUTE_FORCE_STEPS).then(|| part2_brute_force(input, steps) as usize)
+}
+
+// Note: I was mainly using this to sanity check my answer, e.g. with
+// `cargo solve 21 --variant brute_force --param steps=500 --param infinite_steps=500`.
 fn part2_brute_force(input: &str, steps: usize) -> u32 {
     let steps_parity = steps % 2 == 0;
     let map = Map::from(input);
@@ -301,11 +317,25 @@ impl Display for Map {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::params::Params;
 
     #[test]
     fn test_part_one() {
-        let result = reachable_in_steps(&advent_of_code::template::read_file("examples", DAY), 6);
-        assert_eq!(result, 16);
+        let result = params::with(&Params::from([("steps", "6")]), || {
+            part_one(&advent_of_code::template::read_file("examples", DAY))
+        });
+        assert_eq!(result, Some(16));
+    }
+
+    #[test]
+    fn test_part_two_other_steps() {
+        let input = &advent_of_code::template::read_file("examples", DAY);
+        for steps in ["3", "7"] {
+            let result = params::with(&Params::from([("infinite_steps", steps)]), || {
+                part_two(input)
+            });
+            assert_eq!(result, None, "{steps} steps");
+        }
     }
 
     #[test]
@@ -1,10 +1,15 @@
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use tinyvec::TinyVec;
 
-advent_of_code::solution!(22);
+advent_of_code::solution!(22, parse -> Tower);
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let tower = build_tower(input);
+pub fn parse(input: &str) -> Tower {
+    let (mut bricks, (max_x, max_y)) = parse_input(input);
+    bricks.sort_unstable();
+    Tower::from_bricks(bricks, max_x, max_y)
+}
+
+pub fn part_one(tower: &Tower) -> Option<u32> {
     Some(
         (0..tower.bricks.len())
             .filter(|&brick| {
@@ -16,8 +21,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     )
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let tower = build_tower(input);
+pub fn part_two(tower: &Tower) -> Option<u32> {
     Some(
         (0..tower.bricks.len())
             .into_par_iter()
@@ -51,12 +55,6 @@ pub fn part_two(input: &str) -> Option<u32> {
     )
 }
 
-fn build_tower(input: &str) -> Tower {
-    let (mut bricks, (max_x, max_y)) = parse_input(input);
-    bricks.sort_unstable();
-    Tower::from_bricks(bricks, max_x, max_y)
-}
-
 fn parse_input(input: &str) -> (Vec<Brick>, (u16, u16)) {
     let mut input = input.as_bytes();
     let mut ret = Vec::with_capacity(input.len() / 16); // guess at total length
@@ -139,7 +137,7 @@ struct Brick {
 }
 
 #[derive(Debug, Clone)]
-struct Tower {
+pub struct Tower {
     bricks: Vec<Brick>,
     supports: Vec<TinyVec<[usize; 4]>>,
     supported: Vec<TinyVec<[usize; 4]>>,
@@ -147,7 +145,7 @@ struct Tower {
 }
 
 impl Tower {
-    pub fn from_bricks(bricks: Vec<Brick>, max_x: u16, max_y: u16) -> Self {
+    fn from_bricks(bricks: Vec<Brick>, max_x: u16, max_y: u16) -> Self {
         let mut ret = Self {
             top_view: Grid2::new(max_x, max_y),
             supports: vec![Default::default(); bricks.len()],
@@ -223,9 +221,7 @@ impl Brick {
         let mut next = Some(self.ends[0].xy());
         let end = self.ends[1].xy();
         std::iter::from_fn(move || {
-            let Some(ret) = next else {
-                return None;
-            };
+            let ret = next?;
             if ret.x < end.x {
                 next = Some(Point2::new(ret.x + 1, ret.y));
             } else if ret.y < end.y {
@@ -264,13 +260,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(5));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(7));
     }
 }
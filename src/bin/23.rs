@@ -6,10 +6,28 @@ use std::{
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use tinyvec::ArrayVec;
 
-advent_of_code::solution!(23);
+advent_of_code::solution!(23, parse -> Trails, variants = [recursive: 2 => part_two_recursive]);
 
-pub fn part_one(input: &str) -> Option<u16> {
-    let (graph, start, goal) = build_graph(input, true);
+/// The hiking trails as graphs with and without obeying the slopes, each with their start and goal vertex.
+pub struct Trails {
+    slippery: (Graph, usize, usize),
+    dry: (Graph, usize, usize),
+    /// The dry graph with the paths leading to the start and goal trimmed off, and the length of those paths.
+    trimmed: (Graph, usize, usize, u16),
+}
+
+pub fn parse(input: &str) -> Trails {
+    let dry = build_graph(input, false);
+    Trails {
+        slippery: build_graph(input, true),
+        trimmed: trim(dry.clone()),
+        dry,
+    }
+}
+
+pub fn part_one(trails: &Trails) -> Option<u16> {
+    let (graph, start, goal) = &trails.slippery;
+    let (start, goal) = (*start, *goal);
 
     // Dijkstras with negative costs to find the maximal path.
     let mut best_so_far = vec![0; graph.vertices];
@@ -28,30 +46,12 @@ pub fn part_one(input: &str) -> Option<u16> {
     Some(best_so_far[goal])
 }
 
-pub fn part_two(input: &str) -> Option<u16> {
-    let (mut graph, mut start, mut goal) = build_graph(input, false);
+pub fn part_two(trails: &Trails) -> Option<u16> {
+    let (graph, start, goal, trimmed_length) = &trails.trimmed;
+    let (start, goal, trimmed_length) = (*start, *goal, *trimmed_length);
 
     debug_assert!(graph.vertices <= 64, "some optimizations only work with 64 vertices or less. plus it will be too slow with high vertex counts");
 
-    // The start and goal usually only has one connection, so trim it to save on the search space.
-    let mut trimmed_length = 0;
-    let mut trim = |mut cur: usize| {
-        while graph.adjacency[cur].len() == 1 {
-            let (new_cur, distance) = graph.adjacency[cur][0];
-            trimmed_length += distance;
-            graph.adjacency[cur].clear();
-            let idx = graph.adjacency[new_cur]
-                .iter()
-                .position(|&(adj, _)| adj == cur)
-                .unwrap();
-            graph.adjacency[new_cur].remove(idx);
-            cur = new_cur;
-        }
-        cur
-    };
-    goal = trim(goal);
-    start = trim(start);
-
     // Find all paths to depth N iteratively to use rayon on the resulting paths.
     const PRESEARCH_DEPTH: u8 = 8;
     let mut paths = Vec::new();
@@ -73,11 +73,23 @@ pub fn part_two(input: &str) -> Option<u16> {
     paths
         .into_par_iter()
         .map(|(start, visited, distance)| {
-            trimmed_length + part_two_recursive_brute_force(&graph, start, goal, visited, distance)
+            trimmed_length + part_two_recursive_brute_force(graph, start, goal, visited, distance)
         })
         .max()
 }
 
+/// Searches every path on a single thread, without trimming the start and goal first.
+pub fn part_two_recursive(trails: &Trails) -> Option<u16> {
+    let (graph, start, goal) = &trails.dry;
+    Some(part_two_recursive_brute_force(
+        graph,
+        *start,
+        *goal,
+        1 << start,
+        0,
+    ))
+}
+
 fn part_two_recursive_brute_force(
     graph: &Graph,
     cur: usize,
@@ -107,6 +119,29 @@ fn part_two_recursive_brute_force(
     max
 }
 
+/// The start and goal usually only have one connection, so trim them to save on the search space.
+/// Returns the trimmed graph, its new start and goal, and the length of the trimmed paths.
+fn trim((mut graph, start, goal): (Graph, usize, usize)) -> (Graph, usize, usize, u16) {
+    let mut trimmed_length = 0;
+    let mut trim = |mut cur: usize| {
+        while graph.adjacency[cur].len() == 1 {
+            let (new_cur, distance) = graph.adjacency[cur][0];
+            trimmed_length += distance;
+            graph.adjacency[cur].clear();
+            let idx = graph.adjacency[new_cur]
+                .iter()
+                .position(|&(adj, _)| adj == cur)
+                .unwrap();
+            graph.adjacency[new_cur].remove(idx);
+            cur = new_cur;
+        }
+        cur
+    };
+    let goal = trim(goal);
+    let start = trim(start);
+    (graph, start, goal, trimmed_length)
+}
+
 fn build_graph(input: &str, obey_slopes: bool) -> (Graph, usize, usize) {
     let mut graph = Graph::default();
     let input = input.as_bytes();
@@ -277,13 +312,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(94));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(154));
     }
 }
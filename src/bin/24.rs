@@ -1,3 +1,4 @@
+use advent_of_code::template::params;
 use itertools::Itertools;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
@@ -7,8 +8,8 @@ pub fn part_one(input: &str) -> Option<u32> {
     let hailstones = parse_hailstones(input);
     Some(count_xy_intersections(
         hailstones,
-        200000000000000,
-        400000000000000,
+        params::get("test_area_min", 200000000000000),
+        params::get("test_area_max", 400000000000000),
     ))
 }
 
@@ -123,16 +124,12 @@ fn solve_linear_system<const N: usize>(a: [[f64; N]; N], b: [f64; N]) -> Option<
             return None;
         }
         if i != j {
-            for k in 0..N {
-                let tmp = a[i][k];
-                a[i][k] = a[j][k];
-                a[j][k] = tmp;
-            }
+            a.swap(i, j);
             b.swap(i, j);
         }
         let factor = a[i][i];
-        for k in 0..N {
-            a[i][k] /= factor;
+        for x in &mut a[i] {
+            *x /= factor;
         }
         b[i] /= factor;
 
@@ -142,8 +139,9 @@ fn solve_linear_system<const N: usize>(a: [[f64; N]; N], b: [f64; N]) -> Option<
             if factor.abs() < EPSILON {
                 continue;
             }
-            for k in 0..N {
-                a[j][k] += factor * a[i][k];
+            let pivot_row = a[i];
+            for (x, pivot) in a[j].iter_mut().zip(pivot_row) {
+                *x += factor * pivot;
             }
             b[j] += factor * b[i];
         }
@@ -255,12 +253,15 @@ impl Eq for FloatAbs {}
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::params::Params;
 
     #[test]
     fn test_part_one() {
-        let hailstones = parse_hailstones(&advent_of_code::template::read_file("examples", DAY));
-        let result = count_xy_intersections(hailstones, 7, 27);
-        assert_eq!(result, 2);
+        let params = Params::from([("test_area_min", "7"), ("test_area_max", "27")]);
+        let result = params::with(&params, || {
+            part_one(&advent_of_code::template::read_file("examples", DAY))
+        });
+        assert_eq!(result, Some(2));
     }
 
     #[test]
@@ -0,0 +1,87 @@
+//! Runs every solution in a single process. Used by `cargo all` and `cargo time`.
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+fn main() {
+    advent_of_code::template::aggregate::run(SOLUTIONS);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SOLUTIONS;
+
+    #[test]
+    fn test_examples() {
+        let failures: Vec<String> = SOLUTIONS
+            .iter()
+            .flat_map(advent_of_code::template::examples::check_examples)
+            .collect();
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+
+    #[test]
+    fn test_differential() {
+        use advent_of_code::{generate, template::differential};
+
+        let options = differential::Options {
+            cases: 4,
+            seed: 0,
+            max_size: 3,
+        };
+        let failures: Vec<String> = SOLUTIONS
+            .iter()
+            .filter_map(|s| Some((s, generate::generator(s.year, s.day)?)))
+            .flat_map(|(s, generator)| {
+                differential::check(s, generator, &options)
+                    .into_iter()
+                    .filter(|report| !report.is_ok())
+                    .map(move |report| format!("Day {}: {report}", s.day))
+            })
+            .collect();
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+
+    #[test]
+    fn test_generated() {
+        use advent_of_code::{
+            generate,
+            template::{answers::AnswerStatus, panics, params},
+        };
+
+        let mut failures = vec![];
+        for s in SOLUTIONS {
+            let Some(generator) = generate::generator(s.year, s.day) else {
+                continue;
+            };
+            for (size, seed) in [(1, 0), (2, 1), (5, 2)] {
+                let generated = generate::generate(generator, size, seed);
+                let answers = params::with(&generated.params, || {
+                    panics::catch(|| (s.solve)(&generated.input))
+                });
+                let answers = match answers {
+                    Ok(answers) => answers,
+                    Err(e) => {
+                        failures.push(format!("Day {} (size {size}, seed {seed}): {e}", s.day));
+                        continue;
+                    }
+                };
+                for &part in s.metadata.parts {
+                    let failure = match &answers[usize::from(part) - 1] {
+                        Ok(Some(answer)) => match generated.answers.check(part, Some(answer)) {
+                            AnswerStatus::Incorrect(expected) => {
+                                format!("got {answer}, expected {expected}")
+                            }
+                            AnswerStatus::Correct | AnswerStatus::Unknown => continue,
+                        },
+                        Ok(None) => "no answer".to_string(),
+                        Err(e) => e.clone(),
+                    };
+                    failures.push(format!(
+                        "Day {} part {part} (size {size}, seed {seed}): {failure}",
+                        s.day
+                    ));
+                }
+            }
+        }
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+}
@@ -0,0 +1,47 @@
+/// Calibration documents for day 1.
+///
+/// Lines mix lowercase letters, digits and spelled out digits, which sometimes overlap like `eightwo`. Every line has
+/// at least one digit, so part one finds a value on each of them.
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+
+use super::Generated;
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+const OVERLAPS: [&str; 8] = [
+    "oneight",
+    "twone",
+    "threeight",
+    "fiveight",
+    "sevenine",
+    "eightwo",
+    "eighthree",
+    "nineight",
+];
+
+/// `size` is the number of lines.
+pub fn generate(rng: &mut StdRng, size: usize) -> Generated {
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let mut line = String::new();
+        let tokens = rng.gen_range(2..=12);
+        let digit_at = rng.gen_range(0..tokens);
+
+        for i in 0..tokens {
+            match rng.gen_range(0..10) {
+                _ if i == digit_at => line.push(char::from(rng.gen_range(b'1'..=b'9'))),
+                0..=4 => line.push(char::from(rng.gen_range(b'a'..=b'z'))),
+                5 | 6 => line.push(char::from(rng.gen_range(b'1'..=b'9'))),
+                7 | 8 => line.push_str(WORDS.choose(rng).unwrap()),
+                _ => line.push_str(OVERLAPS.choose(rng).unwrap()),
+            }
+        }
+
+        input.push_str(&line);
+        input.push('\n');
+    }
+
+    input.into()
+}
@@ -0,0 +1,31 @@
+/// Cube games for day 2.
+///
+/// Each game reveals a few handfuls of up to 20 cubes per color, so some games are possible with the bag of part one
+/// and some aren't.
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+
+use super::Generated;
+
+/// `size` is the number of games.
+pub fn generate(rng: &mut StdRng, size: usize) -> Generated {
+    let mut input = String::new();
+
+    for id in 1..=size {
+        let reveals: Vec<String> = (0..rng.gen_range(1..=6))
+            .map(|_| {
+                let mut colors = ["red", "green", "blue"];
+                colors.shuffle(rng);
+                let count = rng.gen_range(1..=3);
+                colors[..count]
+                    .iter()
+                    .map(|color| format!("{} {color}", rng.gen_range(1..=20)))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            })
+            .collect();
+
+        input.push_str(&format!("Game {id}: {}\n", reveals.join("; ")));
+    }
+
+    input.into()
+}
@@ -0,0 +1,38 @@
+/// Engine schematics for day 3.
+///
+/// Numbers of up to three digits are scattered over the schematic between symbols, some of them gears next to two
+/// numbers. Numbers on the same row are always separated.
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+
+use super::Generated;
+
+const SYMBOLS: [char; 10] = ['*', '#', '+', '$', '/', '=', '%', '@', '&', '-'];
+
+/// `size` is the number of rows and columns.
+pub fn generate(rng: &mut StdRng, size: usize) -> Generated {
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let mut line = String::new();
+        while line.len() < size {
+            let is_after_number = line.ends_with(|c: char| c.is_ascii_digit());
+            match rng.gen_range(0..20) {
+                0..=3 if !is_after_number => {
+                    let digits = rng.gen_range(1..=3).min(size - line.len());
+                    let number =
+                        rng.gen_range(10u32.pow(digits as u32 - 1)..10u32.pow(digits as u32));
+                    line.push_str(&number.to_string());
+                }
+                // NOTE: favor gears, so some of them are next to two numbers.
+                4 => line.push('*'),
+                5 => line.push(*SYMBOLS.choose(rng).unwrap()),
+                _ => line.push('.'),
+            }
+        }
+
+        input.push_str(&line);
+        input.push('\n');
+    }
+
+    input.into()
+}
@@ -0,0 +1,54 @@
+/// Scratchcards for day 4.
+///
+/// Like the real input, each card has 10 distinct winning numbers and 25 distinct numbers you have, all below 100.
+/// Cards never win copies of cards past the end of the table.
+use rand::{
+    rngs::StdRng,
+    seq::{index, SliceRandom},
+    Rng,
+};
+
+use super::Generated;
+
+const WINNING: usize = 10;
+const HAVE: usize = 25;
+
+/// `size` is the number of cards.
+pub fn generate(rng: &mut StdRng, size: usize) -> Generated {
+    let width = size.to_string().len();
+    let mut input = String::new();
+
+    for id in 1..=size {
+        let remaining = size - id;
+        let most = rng.gen_range(0..=WINNING).min(remaining);
+        let matches = rng.gen_range(0..=most);
+
+        // NOTE: the first `matches` winning numbers are had, too.
+        let numbers: Vec<usize> = index::sample(rng, 99, WINNING + HAVE)
+            .into_iter()
+            .map(|x| x + 1)
+            .collect();
+        let winning = &numbers[..WINNING];
+        let mut have: Vec<usize> = numbers[..matches]
+            .iter()
+            .chain(&numbers[WINNING..WINNING + HAVE - matches])
+            .copied()
+            .collect();
+        have.shuffle(rng);
+
+        let format = |numbers: &[usize]| {
+            numbers
+                .iter()
+                .map(|x| format!("{x:>2}"))
+                .collect::<Vec<String>>()
+                .join(" ")
+        };
+        input.push_str(&format!(
+            "Card {id:>width$}: {} | {}\n",
+            format(winning),
+            format(&have)
+        ));
+    }
+
+    input.into()
+}
@@ -0,0 +1,65 @@
+/// Almanacs for day 5.
+///
+/// Each of the seven maps cuts the numbers below `4_000_000_000` into ranges and moves some of them to where others
+/// were, so every number maps to exactly one number and every location fits into a `u32`, like the real input.
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+
+use super::Generated;
+
+const MAX: u64 = 4_000_000_000;
+const MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+/// `size` is the number of seed ranges, and roughly the number of ranges of each map.
+pub fn generate(rng: &mut StdRng, size: usize) -> Generated {
+    let mut seeds = vec![];
+    for _ in 0..size {
+        let start = rng.gen_range(0..MAX - 1);
+        let len = rng.gen_range(1..=(MAX - start).min(MAX / size as u64 / 4).max(1));
+        seeds.extend([start.to_string(), len.to_string()]);
+    }
+
+    let mut input = format!("seeds: {}\n", seeds.join(" "));
+
+    for name in MAPS {
+        let ranges = size + rng.gen_range(1..=size.max(2));
+        let mut cuts: Vec<u64> = (0..ranges - 1).map(|_| rng.gen_range(1..MAX)).collect();
+        cuts.extend([0, MAX]);
+        cuts.sort_unstable();
+        cuts.dedup();
+        let ranges: Vec<(u64, u64)> = cuts.windows(2).map(|w| (w[0], w[1] - w[0])).collect();
+
+        // NOTE: laying out the ranges in another order keeps the mapping one to one.
+        let mut order: Vec<usize> = (0..ranges.len()).collect();
+        order.shuffle(rng);
+        if order.is_sorted() {
+            // NOTE: the solution expects every map to have at least one range.
+            order.reverse();
+        }
+        let mut destinations = vec![0; ranges.len()];
+        let mut next = 0;
+        for &i in &order {
+            destinations[i] = next;
+            next += ranges[i].1;
+        }
+
+        let mut lines: Vec<String> = ranges
+            .iter()
+            .zip(destinations)
+            .filter(|&(&(source, _), destination)| source != destination)
+            .map(|(&(source, len), destination)| format!("{destination} {source} {len}\n"))
+            .collect();
+        lines.shuffle(rng);
+
+        input.push_str(&format!("\n{name} map:\n{}", lines.concat()));
+    }
+
+    input.into()
+}
@@ -0,0 +1,39 @@
+/// Boat race sheets for day 6.
+///
+/// Like the real input, races last below 100 milliseconds and every record can be beaten. There are at most four
+/// races, so the single race of part two still fits into a `u64`.
+use rand::{rngs::StdRng, Rng};
+
+use super::Generated;
+
+const MAX_RACES: usize = 4;
+
+/// `size` is the number of races, up to four.
+pub fn generate(rng: &mut StdRng, size: usize) -> Generated {
+    let mut times = vec![];
+    let mut distances = vec![];
+
+    for _ in 0..size.min(MAX_RACES) {
+        let time: u64 = rng.gen_range(7..100);
+        // NOTE: holding the button for half the race goes furthest, so the record has to be below that.
+        let best = time * time / 4;
+        let distance = rng.gen_range(best / 4..best);
+        times.push(time.to_string());
+        distances.push(distance.to_string());
+    }
+
+    let width = distances.iter().map(String::len).max().unwrap_or(0);
+    let pad = |values: &[String]| -> String {
+        values
+            .iter()
+            .map(|x| format!("{x:>width$}"))
+            .collect::<Vec<String>>()
+            .join("   ")
+    };
+    format!(
+        "Time:      {}\nDistance:  {}\n",
+        pad(&times),
+        pad(&distances)
+    )
+    .into()
+}
@@ -0,0 +1,32 @@
+/// Camel Cards hands for day 7.
+///
+/// Hands are distinct and cover every type, with jokers mixed in. Bids are at most `1000`, like the real input.
+use std::collections::HashSet;
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+
+use super::Generated;
+
+const CARDS: &[u8; 13] = b"23456789TJQKA";
+/// Most hands, so the total winnings still fit into a `u32`.
+const MAX_HANDS: usize = 3000;
+
+/// `size` is the number of hands.
+pub fn generate(rng: &mut StdRng, size: usize) -> Generated {
+    let mut hands = HashSet::new();
+    let mut input = String::new();
+
+    while hands.len() < size.min(MAX_HANDS) {
+        // NOTE: drawing from a few distinct cards makes pairs and better hands as common as in the real input.
+        let distinct = rng.gen_range(1..=5);
+        let pool: Vec<u8> = CARDS.choose_multiple(rng, distinct).copied().collect();
+        let hand: Vec<u8> = (0..5).map(|_| *pool.choose(rng).unwrap()).collect();
+        let hand = String::from_utf8(hand).unwrap();
+
+        if hands.insert(hand.clone()) {
+            input.push_str(&format!("{hand} {}\n", rng.gen_range(1..=1000)));
+        }
+    }
+
+    input.into()
+}
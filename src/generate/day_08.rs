@@ -0,0 +1,94 @@
+/// Networks of ghost paths for day 8.
+///
+/// Every ghost walks a loop from its `..A` node to its `..Z` node, which then leads back to the second node of the
+/// loop, like the real input. Loops are a prime multiple of the number of instructions long, so each node of a loop
+/// is always left with the same instruction, and the other direction leads to a random node. `AAA` and `ZZZ` belong
+/// to the first loop.
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+
+use super::Generated;
+
+const PRIMES: [usize; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+/// Most nodes of all loops, well below the `26 * 26 * 24` names that don't end with `A` or `Z`.
+const MAX_NODES: usize = 12_000;
+
+/// `size` is the number of instructions.
+pub fn generate(rng: &mut StdRng, size: usize) -> Generated {
+    let ghosts = rng.gen_range(1..=6);
+    let primes: Vec<usize> = PRIMES.choose_multiple(rng, ghosts).copied().collect();
+    let instructions_len = size.min(MAX_NODES / primes.iter().sum::<usize>()).max(1);
+    let instructions: Vec<bool> = (0..instructions_len).map(|_| rng.gen()).collect();
+
+    let mut names = Names::default();
+    let mut loops: Vec<Vec<String>> = vec![];
+    for (i, prime) in primes.iter().enumerate() {
+        let (start, destination) = if i == 0 {
+            ("AAA".to_string(), "ZZZ".to_string())
+        } else {
+            (names.ending_with(rng, b'A'), names.ending_with(rng, b'Z'))
+        };
+
+        let len = prime * instructions_len;
+        let mut nodes = vec![start];
+        nodes.extend((1..len).map(|_| names.middle(rng)));
+        nodes.push(destination);
+        loops.push(nodes);
+    }
+
+    let all: Vec<&String> = loops.iter().flatten().collect();
+    let mut lines: Vec<String> = vec![];
+    for nodes in &loops {
+        for (j, node) in nodes.iter().enumerate() {
+            // NOTE: the destination is reached after a multiple of the instructions and leads back into the loop.
+            let next = nodes.get(j + 1).unwrap_or(&nodes[1]);
+            let other = all[rng.gen_range(0..all.len())];
+            let is_right = instructions[j % instructions_len];
+            let (left, right) = if is_right {
+                (other, next)
+            } else {
+                (next, other)
+            };
+            lines.push(format!("{node} = ({left}, {right})"));
+        }
+    }
+    lines.shuffle(rng);
+
+    let instructions: String = instructions
+        .iter()
+        .map(|&is_right| if is_right { 'R' } else { 'L' })
+        .collect();
+    format!("{instructions}\n\n{}\n", lines.join("\n")).into()
+}
+
+/// Hands out unique node names.
+#[derive(Default)]
+struct Names(std::collections::HashSet<[u8; 3]>);
+
+impl Names {
+    fn ending_with(&mut self, rng: &mut StdRng, last: u8) -> String {
+        loop {
+            let name = [rng.gen_range(b'A'..=b'Z'), rng.gen_range(b'A'..=b'Z'), last];
+            if let Some(name) = self.insert(name) {
+                return name;
+            }
+        }
+    }
+
+    fn middle(&mut self, rng: &mut StdRng) -> String {
+        loop {
+            let name = [
+                rng.gen_range(b'A'..=b'Z'),
+                rng.gen_range(b'A'..=b'Z'),
+                rng.gen_range(b'B'..=b'Y'),
+            ];
+            if let Some(name) = self.insert(name) {
+                return name;
+            }
+        }
+    }
+
+    fn insert(&mut self, name: [u8; 3]) -> Option<String> {
+        let is_reserved = name == *b"AAA" || name == *b"ZZZ";
+        (!is_reserved && self.0.insert(name)).then(|| String::from_utf8(name.to_vec()).unwrap())
+    }
+}
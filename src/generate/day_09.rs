@@ -0,0 +1,40 @@
+/// OASIS reports for day 9.
+///
+/// Every history of 21 values is a polynomial of a degree up to 8 with small coefficients, so the differences reach
+/// zero and all values, including the extrapolated ones, fit into an `i32`.
+use rand::{rngs::StdRng, Rng};
+
+use super::Generated;
+
+const VALUES: i64 = 21;
+
+/// `size` is the number of histories.
+pub fn generate(rng: &mut StdRng, size: usize) -> Generated {
+    let mut input = String::new();
+
+    for _ in 0..size {
+        // NOTE: a sum of binomial coefficients `C(x, k)` has integer values, whose k-th differences are constant.
+        let coefficients: Vec<i64> = (0..=rng.gen_range(0..=8))
+            .map(|_| rng.gen_range(-20..=20))
+            .collect();
+        let values: Vec<String> = (0..VALUES)
+            .map(|x| {
+                coefficients
+                    .iter()
+                    .enumerate()
+                    .map(|(k, c)| c * binomial(x, k as i64))
+                    .sum::<i64>()
+                    .to_string()
+            })
+            .collect();
+
+        input.push_str(&values.join(" "));
+        input.push('\n');
+    }
+
+    input.into()
+}
+
+fn binomial(n: i64, k: i64) -> i64 {
+    (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
@@ -0,0 +1,163 @@
+/// Pipe mazes for day 10.
+///
+/// The loop traces the outline of a random blob of tiles, which has no holes and never touches itself diagonally, so
+/// the outline is a single loop enclosing the blob. Tiles off the loop are random pipes and ground. Like the real
+/// input, only the two neighbors of `S` on the loop connect to it.
+use rand::{rngs::StdRng, Rng};
+
+use super::Generated;
+
+/// Directions a pipe connects, as bits.
+const NORTH: u8 = 1;
+const SOUTH: u8 = 2;
+const EAST: u8 = 4;
+const WEST: u8 = 8;
+
+/// `size` is the number of rows and columns, minus one.
+pub fn generate(rng: &mut StdRng, size: usize) -> Generated {
+    let blob = grow_blob(rng, size);
+    let side = size + 1;
+
+    // NOTE: the corners of the blob's tiles are the tiles of the maze, the blob's edges become the loop.
+    let is_blob = |row: usize, col: usize| row < size && col < size && blob[row * size + col];
+    let mut tiles = vec![0; side * side];
+    for row in 0..side {
+        for col in 0..side {
+            let above = |col: usize| row > 0 && is_blob(row - 1, col);
+            let below = |col: usize| is_blob(row, col);
+            let left = |row: usize| col > 0 && is_blob(row, col - 1);
+            let right = |row: usize| is_blob(row, col);
+
+            let mut directions = 0;
+            if row > 0 && left(row - 1) != right(row - 1) {
+                directions |= NORTH;
+            }
+            if left(row) != right(row) {
+                directions |= SOUTH;
+            }
+            if col > 0 && above(col - 1) != below(col - 1) {
+                directions |= WEST;
+            }
+            if above(col) != below(col) {
+                directions |= EAST;
+            }
+            tiles[row * side + col] = directions;
+        }
+    }
+
+    let on_loop: Vec<usize> = (0..tiles.len()).filter(|&i| tiles[i] != 0).collect();
+    let start = on_loop[rng.gen_range(0..on_loop.len())];
+
+    for (i, tile) in tiles.iter_mut().enumerate() {
+        if *tile == 0 && !rng.gen_bool(0.3) {
+            *tile = [
+                NORTH | SOUTH,
+                EAST | WEST,
+                NORTH | EAST,
+                NORTH | WEST,
+                SOUTH | WEST,
+                SOUTH | EAST,
+            ][rng.gen_range(0..6)];
+            // NOTE: junk next to the start must not connect to it.
+            let (row, col) = (i / side, i % side);
+            let (start_row, start_col) = (start / side, start % side);
+            let points_at_start = (*tile & SOUTH != 0 && (row + 1, col) == (start_row, start_col))
+                || (*tile & NORTH != 0 && row > 0 && (row - 1, col) == (start_row, start_col))
+                || (*tile & EAST != 0 && (row, col + 1) == (start_row, start_col))
+                || (*tile & WEST != 0 && col > 0 && (row, col - 1) == (start_row, start_col));
+            if points_at_start {
+                *tile = 0;
+            }
+        }
+    }
+
+    let mut input = String::new();
+    for row in 0..side {
+        for col in 0..side {
+            let i = row * side + col;
+            input.push(if i == start { 'S' } else { pipe(tiles[i]) });
+        }
+        input.push('\n');
+    }
+    input.into()
+}
+
+fn pipe(directions: u8) -> char {
+    match directions {
+        0 => '.',
+        x if x == NORTH | SOUTH => '|',
+        x if x == EAST | WEST => '-',
+        x if x == NORTH | EAST => 'L',
+        x if x == NORTH | WEST => 'J',
+        x if x == SOUTH | WEST => '7',
+        x if x == SOUTH | EAST => 'F',
+        _ => unreachable!("tiles connect two directions"),
+    }
+}
+
+/// Grows a blob over about half of a `size` by `size` grid from its centre. Tiles are only added if their neighbors
+/// in the blob form a single run around them that includes an edge neighbor, which keeps the blob free of holes and
+/// diagonal contacts.
+fn grow_blob(rng: &mut StdRng, size: usize) -> Vec<bool> {
+    let mut blob = vec![false; size * size];
+    let (row, col) = (size / 2, size / 2);
+    blob[row * size + col] = true;
+    let mut frontier = vec![];
+    if row > 0 {
+        frontier.extend([(row - 1) * size + col, row * size + col - 1]);
+    }
+    if row + 1 < size {
+        frontier.extend([(row + 1) * size + col, row * size + col + 1]);
+    }
+    let target = (size * size / 2).max(1);
+    let mut count = 1;
+
+    // Neighbors clockwise from the top left, with whether they share an edge.
+    const RING: [(isize, isize, bool); 8] = [
+        (-1, -1, false),
+        (-1, 0, true),
+        (-1, 1, false),
+        (0, 1, true),
+        (1, 1, false),
+        (1, 0, true),
+        (1, -1, false),
+        (0, -1, true),
+    ];
+
+    let mut attempts = 0;
+    while count < target && !frontier.is_empty() && attempts < 50 * target {
+        attempts += 1;
+        let candidate = frontier.swap_remove(rng.gen_range(0..frontier.len()));
+        if blob[candidate] {
+            continue;
+        }
+
+        let (row, col) = ((candidate / size) as isize, (candidate % size) as isize);
+        let ring = RING.map(|(dr, dc, is_edge)| {
+            let (row, col) = (row + dr, col + dc);
+            let inside = row >= 0 && col >= 0 && (row as usize) < size && (col as usize) < size;
+            (inside && blob[row as usize * size + col as usize], is_edge)
+        });
+        let runs = (0..8)
+            .filter(|&i| ring[i].0 && !ring[(i + 7) % 8].0)
+            .count();
+        let touches_edge = ring.iter().any(|&(in_blob, is_edge)| in_blob && is_edge);
+
+        if runs == 1 && touches_edge {
+            blob[candidate] = true;
+            count += 1;
+            for (dr, dc, is_edge) in RING {
+                let (row, col) = (row + dr, col + dc);
+                if is_edge && row >= 0 && col >= 0 && (row as usize) < size && (col as usize) < size
+                {
+                    frontier.push(row as usize * size + col as usize);
+                }
+            }
+        } else {
+            // NOTE: the tile may fit later, when more of its neighbors are in the blob.
+            frontier.insert(0, candidate);
+        }
+    }
+
+    blob
+}
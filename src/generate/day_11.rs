@@ -0,0 +1,23 @@
+/// Galaxy images for day 11.
+///
+/// Galaxies are sparse, and some rows and columns are left empty so they expand.
+use rand::{rngs::StdRng, Rng};
+
+use super::Generated;
+
+/// `size` is the number of rows and columns.
+pub fn generate(rng: &mut StdRng, size: usize) -> Generated {
+    let empty_rows: Vec<bool> = (0..size).map(|_| rng.gen_bool(0.1)).collect();
+    let empty_cols: Vec<bool> = (0..size).map(|_| rng.gen_bool(0.1)).collect();
+    let density = rng.gen_range(0.01..0.08);
+
+    let mut input = String::new();
+    for &is_empty_row in &empty_rows {
+        for &is_empty_col in &empty_cols {
+            let is_galaxy = !is_empty_row && !is_empty_col && rng.gen_bool(density);
+            input.push(if is_galaxy { '#' } else { '.' });
+        }
+        input.push('\n');
+    }
+    input.into()
+}
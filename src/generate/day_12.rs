@@ -0,0 +1,47 @@
+/// Rows of damaged spring records for day 12.
+///
+/// Each row starts from an arrangement of operational and damaged springs, which gives the group sizes, and then
+/// hides some of the springs behind `?`. Every row has at least one arrangement.
+use rand::{rngs::StdRng, Rng};
+
+use super::Generated;
+
+const MAX_LEN: usize = 20;
+/// Most unknown springs of a row, so trying every arrangement stays feasible.
+const MAX_UNKNOWN: usize = 12;
+
+/// `size` is the number of rows.
+pub fn generate(rng: &mut StdRng, size: usize) -> Generated {
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let len = rng.gen_range(1..=MAX_LEN);
+        let mut springs: Vec<u8> = (0..len)
+            .map(|_| if rng.gen_bool(0.5) { b'#' } else { b'.' })
+            .collect();
+        let first = rng.gen_range(0..len);
+        springs[first] = b'#';
+
+        let groups: Vec<String> = springs
+            .split(|&spring| spring == b'.')
+            .filter(|group| !group.is_empty())
+            .map(|group| group.len().to_string())
+            .collect();
+
+        let unknown_rate = rng.gen_range(0.2..0.8);
+        let mut unknown = 0;
+        for spring in &mut springs {
+            if unknown < MAX_UNKNOWN && rng.gen_bool(unknown_rate) {
+                *spring = b'?';
+                unknown += 1;
+            }
+        }
+
+        input.push_str(std::str::from_utf8(&springs).unwrap());
+        input.push(' ');
+        input.push_str(&groups.join(","));
+        input.push('\n');
+    }
+
+    input.into()
+}
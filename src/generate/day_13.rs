@@ -0,0 +1,94 @@
+/// Patterns of ash and rocks for day 13.
+///
+/// Each pattern mirrors perfectly along exactly one line, and along exactly one other line if a single smudge is
+/// fixed, like the real input. One of the lines is between columns and the other between rows. The pattern is built
+/// symmetric along both lines and then smudged outside of the range the first line mirrors.
+use rand::{rngs::StdRng, Rng};
+
+use super::Generated;
+
+/// `size` is the number of patterns.
+pub fn generate(rng: &mut StdRng, size: usize) -> Generated {
+    let patterns: Vec<String> = (0..size).map(|_| pattern(rng)).collect();
+    (patterns.join("\n\n") + "\n").into()
+}
+
+fn pattern(rng: &mut StdRng) -> String {
+    loop {
+        let (rows, cols) = (rng.gen_range(5..=17), rng.gen_range(5..=17));
+        // NOTE: the perfect line leaves some columns unmirrored, which is where the smudge goes.
+        let col_line = rng.gen_range(1..cols);
+        if col_line * 2 == cols {
+            continue;
+        }
+        let row_line = rng.gen_range(1..rows);
+
+        let mut grid = vec![vec![false; cols]; rows];
+        for row in 0..rows {
+            for col in 0..cols {
+                // NOTE: cells take the value of their mirror images that come first, if there are any.
+                let col_mirror = mirror(col, col_line, cols).filter(|&c| c < col);
+                let row_mirror = mirror(row, row_line, rows).filter(|&r| r < row);
+                grid[row][col] = match (row_mirror, col_mirror) {
+                    (Some(r), _) => grid[r][col],
+                    (None, Some(c)) => grid[row][c],
+                    (None, None) => rng.gen_bool(0.5),
+                };
+            }
+        }
+
+        let unmirrored: Vec<usize> = (0..cols)
+            .filter(|&c| mirror(c, col_line, cols).is_none())
+            .collect();
+        let mirrored_rows: Vec<usize> = (0..rows)
+            .filter(|&r| mirror(r, row_line, rows).is_some())
+            .collect();
+        let smudge = (
+            mirrored_rows[rng.gen_range(0..mirrored_rows.len())],
+            unmirrored[rng.gen_range(0..unmirrored.len())],
+        );
+        grid[smudge.0][smudge.1] = !grid[smudge.0][smudge.1];
+
+        if rng.gen_bool(0.5) {
+            grid = (0..cols)
+                .map(|c| (0..rows).map(|r| grid[r][c]).collect())
+                .collect();
+        }
+
+        // NOTE: other lines can line up by chance, try again then.
+        let differences = line_differences(&grid);
+        if differences.iter().filter(|&&d| d == 0).count() == 1
+            && differences.iter().filter(|&&d| d == 1).count() == 1
+        {
+            let lines: Vec<String> = grid
+                .iter()
+                .map(|row| row.iter().map(|&x| if x { '#' } else { '.' }).collect())
+                .collect();
+            return lines.join("\n");
+        }
+    }
+}
+
+/// The mirror image of `x` along the line before `line`, if it is within `len`.
+fn mirror(x: usize, line: usize, len: usize) -> Option<usize> {
+    let image = (2 * line).checked_sub(x + 1)?;
+    (image < len).then_some(image)
+}
+
+/// Number of cells that differ from their mirror image, for every line between columns and rows.
+fn line_differences(grid: &[Vec<bool>]) -> Vec<usize> {
+    let (rows, cols) = (grid.len(), grid[0].len());
+    let between_cols = (1..cols).map(|line| {
+        (0..rows)
+            .flat_map(|r| (0..line).map(move |c| (r, c)))
+            .filter(|&(r, c)| mirror(c, line, cols).is_some_and(|m| grid[r][c] != grid[r][m]))
+            .count()
+    });
+    let between_rows = (1..rows).map(|line| {
+        (0..line)
+            .flat_map(|r| (0..cols).map(move |c| (r, c)))
+            .filter(|&(r, c)| mirror(r, line, rows).is_some_and(|m| grid[r][c] != grid[m][c]))
+            .count()
+    });
+    between_cols.chain(between_rows).collect()
+}
@@ -0,0 +1,27 @@
+/// Platforms of rocks for day 14.
+///
+/// Platforms are square, like the real input, with rounded rocks on about a fifth of the tiles and cube-shaped rocks
+/// on about a tenth. The solution stores coordinates in a byte, so they are at most 255 tiles wide.
+use rand::{rngs::StdRng, Rng};
+
+use super::Generated;
+
+const MAX_SIDE: usize = 255;
+
+/// `size` is the number of rows and columns.
+pub fn generate(rng: &mut StdRng, size: usize) -> Generated {
+    let side = size.min(MAX_SIDE);
+    let mut input = String::new();
+
+    for _ in 0..side {
+        for _ in 0..side {
+            input.push(match rng.gen_range(0..10) {
+                0 | 1 => 'O',
+                2 => '#',
+                _ => '.',
+            });
+        }
+        input.push('\n');
+    }
+    input.into()
+}
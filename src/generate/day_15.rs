@@ -0,0 +1,30 @@
+/// Initialization sequences for day 15.
+///
+/// Steps insert and remove lenses of a limited set of labels, so labels come back and share boxes.
+use rand::{rngs::StdRng, Rng};
+
+use super::Generated;
+
+/// `size` is the number of steps.
+pub fn generate(rng: &mut StdRng, size: usize) -> Generated {
+    let labels: Vec<String> = (0..size / 4 + 1)
+        .map(|_| {
+            (0..rng.gen_range(2..=6))
+                .map(|_| char::from(rng.gen_range(b'a'..=b'z')))
+                .collect()
+        })
+        .collect();
+
+    let steps: Vec<String> = (0..size)
+        .map(|_| {
+            let label = &labels[rng.gen_range(0..labels.len())];
+            if rng.gen_bool(0.3) {
+                format!("{label}-")
+            } else {
+                format!("{label}={}", rng.gen_range(1..=9))
+            }
+        })
+        .collect();
+
+    (steps.join(",") + "\n").into()
+}
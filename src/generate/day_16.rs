@@ -0,0 +1,24 @@
+/// Contraptions of mirrors and splitters for day 16.
+///
+/// Contraptions are square, like the real input, with a mirror or splitter on about a tenth of the tiles.
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+
+use super::Generated;
+
+/// `size` is the number of rows and columns.
+pub fn generate(rng: &mut StdRng, size: usize) -> Generated {
+    let mut input = String::new();
+
+    for _ in 0..size {
+        for _ in 0..size {
+            let tile = if rng.gen_bool(0.1) {
+                *['/', '\\', '|', '-'].choose(rng).unwrap()
+            } else {
+                '.'
+            };
+            input.push(tile);
+        }
+        input.push('\n');
+    }
+    input.into()
+}
@@ -0,0 +1,24 @@
+/// City maps of heat loss for day 17.
+///
+/// Heat loss is lower close to the diagonal from the start to the end, so the best paths don't just follow the edges.
+/// Maps are at least five blocks wide, so the ultra crucibles of part two can reach the end.
+use rand::{rngs::StdRng, Rng};
+
+use super::Generated;
+
+/// `size` is the number of rows and columns, minus four.
+pub fn generate(rng: &mut StdRng, size: usize) -> Generated {
+    let side = size + 4;
+    let mut input = String::new();
+
+    for row in 0..side {
+        for col in 0..side {
+            // NOTE: the distance from the diagonal decides the lowest heat loss of a block.
+            let off_diagonal = row.abs_diff(col) * 8 / side;
+            let low = 1 + off_diagonal.min(5) as u8;
+            input.push(char::from(b'0' + rng.gen_range(low..=9)));
+        }
+        input.push('\n');
+    }
+    input.into()
+}
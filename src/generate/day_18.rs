@@ -0,0 +1,81 @@
+/// Dig plans for day 18.
+///
+/// Both plans trace a polygon that is a skyline above a mirrored skyline, so the trench never crosses itself and
+/// turns after every instruction. Distances of the first plan fit in two digits, like the fast part one expects.
+/// The second plan is hidden in the colors and has the same number of instructions.
+use rand::{rngs::StdRng, Rng};
+
+use super::Generated;
+
+/// `size` is the number of columns of the skylines, each of them takes four instructions.
+pub fn generate(rng: &mut StdRng, size: usize) -> Generated {
+    let part_1 = skyline(rng, size, 99);
+    let part_2 = skyline(rng, size, 0xfffff);
+    let offset = rng.gen_range(0..part_1.len());
+
+    let mut input = String::new();
+    for i in 0..part_1.len() {
+        let (direction, distance) = part_1[(i + offset) % part_1.len()];
+        let (color_direction, color_distance) = part_2[(i + offset) % part_2.len()];
+        let color_direction = match color_direction {
+            'R' => 0,
+            'D' => 1,
+            'L' => 2,
+            _ => 3,
+        };
+        input.push_str(&format!(
+            "{direction} {distance} (#{color_distance:05x}{color_direction})\n"
+        ));
+    }
+
+    input.into()
+}
+
+/// Instructions for a skyline of `columns` columns, with no distance above `max`.
+fn skyline(rng: &mut StdRng, columns: usize, max: u32) -> Vec<(char, u32)> {
+    let half = max / 2;
+    let heights = |rng: &mut StdRng| {
+        let mut heights: Vec<u32> = vec![rng.gen_range(1..=half)];
+        for _ in 1..columns {
+            let prev = *heights.last().unwrap();
+            let next = loop {
+                let next = rng.gen_range(1..=half);
+                if next != prev {
+                    break next;
+                }
+            };
+            heights.push(next);
+        }
+        heights
+    };
+    let widths: Vec<u32> = (0..columns).map(|_| rng.gen_range(1..=max)).collect();
+    let bottom = heights(rng);
+    let top = heights(rng);
+
+    let mut instructions = vec![];
+    let vertical = |from: u32, to: u32, up: char, down: char| {
+        if to > from {
+            (up, to - from)
+        } else {
+            (down, from - to)
+        }
+    };
+
+    // NOTE: depths below the start are counted downwards, heights upwards.
+    for i in 0..columns {
+        instructions.push(('R', widths[i]));
+        if i + 1 < columns {
+            instructions.push(vertical(bottom[i], bottom[i + 1], 'D', 'U'));
+        }
+    }
+    instructions.push(('U', bottom[columns - 1] + top[columns - 1]));
+    for i in (0..columns).rev() {
+        instructions.push(('L', widths[i]));
+        if i > 0 {
+            instructions.push(vertical(top[i], top[i - 1], 'U', 'D'));
+        }
+    }
+    instructions.push(('D', top[0] + bottom[0]));
+
+    instructions
+}
@@ -0,0 +1,69 @@
+/// Workflows and parts for day 19.
+///
+/// The workflows form a tree rooted at `in`, so every part is accepted or rejected after a few workflows, and every
+/// workflow has at least one rule before its fallback.
+use std::collections::HashSet;
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+
+use super::Generated;
+
+const CATEGORIES: [char; 4] = ['x', 'm', 'a', 's'];
+const MAX_RULES: usize = 4;
+
+/// `size` is the number of workflows, and of parts.
+pub fn generate(rng: &mut StdRng, size: usize) -> Generated {
+    let mut taken = HashSet::from(["in".to_string()]);
+    let mut names = vec!["in".to_string()];
+    while names.len() < size {
+        let name: String = (0..rng.gen_range(2..=3))
+            .map(|_| char::from(rng.gen_range(b'a'..=b'z')))
+            .collect();
+        if taken.insert(name.clone()) {
+            names.push(name);
+        }
+    }
+
+    // NOTE: every workflow is sent to from a workflow earlier in the list, so there are no cycles.
+    let mut children: Vec<Vec<&str>> = vec![vec![]; size];
+    for (i, name) in names.iter().enumerate().skip(1) {
+        children[rng.gen_range(0..i)].push(name);
+    }
+
+    let mut workflows: Vec<String> = names
+        .iter()
+        .zip(children)
+        .map(|(name, children)| {
+            let rules = rng
+                .gen_range(1..=MAX_RULES)
+                .max(children.len().saturating_sub(1));
+            let mut destinations = children;
+            while destinations.len() < rules + 1 {
+                destinations.push(if rng.gen_bool(0.5) { "A" } else { "R" });
+            }
+            destinations.shuffle(rng);
+
+            let fallback = destinations.pop().unwrap();
+            let rules: Vec<String> = destinations
+                .into_iter()
+                .map(|destination| {
+                    let category = CATEGORIES[rng.gen_range(0..CATEGORIES.len())];
+                    let comparison = if rng.gen_bool(0.5) { '<' } else { '>' };
+                    let value = rng.gen_range(1..=4000);
+                    format!("{category}{comparison}{value}:{destination}")
+                })
+                .collect();
+            format!("{name}{{{},{fallback}}}", rules.join(","))
+        })
+        .collect();
+    workflows.shuffle(rng);
+
+    let mut input = workflows.join("\n");
+    input.push_str("\n\n");
+    for _ in 0..size {
+        let [x, m, a, s] = [(); 4].map(|_| rng.gen_range(1..=4000));
+        input.push_str(&format!("{{x={x},m={m},a={a},s={s}}}\n"));
+    }
+
+    input.into()
+}
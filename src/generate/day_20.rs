@@ -0,0 +1,87 @@
+/// Module configurations for day 20.
+///
+/// Like the real input, the broadcaster feeds chains of flip-flops that count button presses. Each chain has a
+/// conjunction that resets the count after a period and then sends a low pulse through an inverter, and the inverters
+/// all feed the conjunction in front of `rx`. `rx` first gets a low pulse after the least common multiple of the
+/// periods, the answer of part two.
+use std::collections::HashSet;
+
+use num_integer::Integer;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+
+use super::Generated;
+use crate::template::answers::Answers;
+
+const CHAINS: usize = 4;
+/// Most flip-flops of a chain, so the network fits the 64 modules the solution has room for.
+const MAX_BITS: usize = 12;
+
+/// `size` is the number of flip-flops of each chain, minus one.
+pub fn generate(rng: &mut StdRng, size: usize) -> Generated {
+    let bits = (size + 1).min(MAX_BITS);
+    let mut names = Names::default();
+    let mut lines = vec![];
+
+    let last = names.next(rng);
+    lines.push(format!("&{last} -> rx"));
+
+    let mut firsts = vec![];
+    let mut presses = 1u64;
+    for _ in 0..CHAINS {
+        // NOTE: the period has its lowest and highest bit set, so the first and last flip-flop feed the hub.
+        let period = rng.gen_range(1 << (bits - 1)..1 << bits) | 1;
+        presses = presses.lcm(&period);
+        let flip_flops: Vec<String> = (0..bits).map(|_| names.next(rng)).collect();
+        let hub = names.next(rng);
+        let inverter = names.next(rng);
+
+        let mut hub_outputs = vec![inverter.clone()];
+        for (bit, flip_flop) in flip_flops.iter().enumerate() {
+            let mut outputs = vec![];
+            if let Some(next) = flip_flops.get(bit + 1) {
+                outputs.push(next.clone());
+            }
+            if period & (1 << bit) != 0 {
+                outputs.push(hub.clone());
+            }
+            if bit == 0 || period & (1 << bit) == 0 {
+                hub_outputs.push(flip_flop.clone());
+            }
+            outputs.shuffle(rng);
+            lines.push(format!("%{flip_flop} -> {}", outputs.join(", ")));
+        }
+        hub_outputs.shuffle(rng);
+        lines.push(format!("&{hub} -> {}", hub_outputs.join(", ")));
+        lines.push(format!("&{inverter} -> {last}"));
+        firsts.push(flip_flops[0].clone());
+    }
+    lines.push(format!("broadcaster -> {}", firsts.join(", ")));
+    lines.shuffle(rng);
+
+    Generated {
+        answers: Answers {
+            part_1: None,
+            part_2: Some(presses.to_string()),
+        },
+        ..(lines.join("\n") + "\n").into()
+    }
+}
+
+/// Hands out distinct two letter names.
+#[derive(Default)]
+struct Names(HashSet<String>);
+
+impl Names {
+    fn next(&mut self, rng: &mut StdRng) -> String {
+        loop {
+            // NOTE: names starting with `b` would be read as the broadcaster.
+            let name: String = [rng.gen_range(b'c'..=b'z'), rng.gen_range(b'a'..=b'z')]
+                .map(char::from)
+                .iter()
+                .collect();
+            if name != "rx" && self.0.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+}
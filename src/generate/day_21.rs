@@ -0,0 +1,109 @@
+/// Garden maps for day 21.
+///
+/// Like the real input the map is square with an odd side, the start is in its centre, and the middle row, the middle
+/// column, the border and a diamond through the middles of the edges are free of rocks. Every garden can be reached
+/// without a detour, gardens that can't are filled in.
+use rand::{rngs::StdRng, Rng};
+
+use super::Generated;
+use crate::template::answers::Answers;
+use crate::template::params::Params;
+
+/// Most gardens from the centre to an edge, so walking the infinite map a few times over stays feasible.
+const MAX_HALF: usize = 65;
+
+/// The centre is `2 * size + 1` gardens away from each edge. Like `65` of the real input, this is odd.
+pub fn generate(rng: &mut StdRng, size: usize) -> Generated {
+    let half = (2 * size + 1).min(MAX_HALF);
+    let side = 2 * half + 1;
+    let rock_rate = rng.gen_range(0.05..0.25);
+
+    let mut is_rock = vec![false; side * side];
+    for row in 0..side {
+        for col in 0..side {
+            let distance = row.abs_diff(half) + col.abs_diff(half);
+            let is_clear = row == half
+                || col == half
+                || row == 0
+                || col == 0
+                || row == side - 1
+                || col == side - 1
+                || distance.abs_diff(half) <= 1;
+            is_rock[row * side + col] = !is_clear && rng.gen_bool(rock_rate);
+        }
+    }
+
+    // NOTE: the infinite map is entered at the centre, the corners and the middles of the edges of each copy. Like
+    // the real input, no garden should take a detour to reach from any of them, which part two relies on. Filling in
+    // gardens can cause detours from the other entrances, so repeat until nothing changes.
+    let entrances = [0, half, side - 1];
+    let mut is_changed = true;
+    while is_changed {
+        is_changed = false;
+        for row in entrances {
+            for col in entrances {
+                let is_reached = reach_directly(&is_rock, side, (row, col));
+                for (is_rock, is_reached) in is_rock.iter_mut().zip(is_reached) {
+                    if !*is_rock && !is_reached {
+                        *is_rock = true;
+                        is_changed = true;
+                    }
+                }
+            }
+        }
+    }
+
+    let mut input = String::new();
+    for row in 0..side {
+        for col in 0..side {
+            input.push(match (row, col) {
+                _ if (row, col) == (half, half) => 'S',
+                _ if is_rock[row * side + col] => '#',
+                _ => '.',
+            });
+        }
+        input.push('\n');
+    }
+
+    // NOTE: part two only works for an odd number of steps that ends on the edge of a map, after an even number of
+    // maps.
+    let steps = rng.gen_range(1..=half);
+    let infinite_steps = half + side * 2 * rng.gen_range(1..=2);
+    Generated {
+        input,
+        params: Params::from([
+            ("steps", steps.to_string().as_str()),
+            ("infinite_steps", infinite_steps.to_string().as_str()),
+        ]),
+        answers: Answers::default(),
+    }
+}
+
+/// Which tiles can be reached from `from` on a shortest path, i.e. only walking towards them.
+fn reach_directly(is_rock: &[bool], side: usize, from: (usize, usize)) -> Vec<bool> {
+    let mut tiles: Vec<(usize, usize)> = (0..side)
+        .flat_map(|row| (0..side).map(move |col| (row, col)))
+        .collect();
+    tiles.sort_by_key(|&(row, col)| row.abs_diff(from.0) + col.abs_diff(from.1));
+
+    let towards = |x: usize, to: usize| match x.cmp(&to) {
+        std::cmp::Ordering::Less => Some(x + 1),
+        std::cmp::Ordering::Equal => None,
+        std::cmp::Ordering::Greater => Some(x - 1),
+    };
+
+    let mut is_reached = vec![false; side * side];
+    for (row, col) in tiles {
+        let closer = [
+            towards(row, from.0).map(|row| (row, col)),
+            towards(col, from.1).map(|col| (row, col)),
+        ];
+        is_reached[row * side + col] = !is_rock[row * side + col]
+            && ((row, col) == from
+                || closer
+                    .into_iter()
+                    .flatten()
+                    .any(|(row, col)| is_reached[row * side + col]));
+    }
+    is_reached
+}
@@ -0,0 +1,48 @@
+/// Snapshots of falling bricks for day 22.
+///
+/// Bricks are dropped one at a time onto a 10 by 10 area, each starting a little above everything below it, so no
+/// two bricks overlap. The snapshot lists them in random order.
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+
+use super::Generated;
+
+const WIDTH: usize = 10;
+const MAX_LEN: usize = 5;
+
+/// `size` is the number of bricks.
+pub fn generate(rng: &mut StdRng, size: usize) -> Generated {
+    let mut heights = [[0; WIDTH]; WIDTH];
+    let mut bricks = vec![];
+
+    for _ in 0..size {
+        let len = rng.gen_range(1..=MAX_LEN);
+        let axis = rng.gen_range(0..3);
+        let (x_len, y_len, z_len) = match axis {
+            0 => (len, 1, 1),
+            1 => (1, len, 1),
+            _ => (1, 1, len),
+        };
+        let x = rng.gen_range(0..=WIDTH - x_len);
+        let y = rng.gen_range(0..=WIDTH - y_len);
+
+        let below = (x..x + x_len)
+            .flat_map(|x| (y..y + y_len).map(move |y| (x, y)))
+            .map(|(x, y)| heights[x][y])
+            .max()
+            .unwrap();
+        let z = below + 1 + rng.gen_range(0..=3);
+        for row in &mut heights[x..x + x_len] {
+            row[y..y + y_len].fill(z + z_len - 1);
+        }
+
+        bricks.push(format!(
+            "{x},{y},{z}~{},{},{}",
+            x + x_len - 1,
+            y + y_len - 1,
+            z + z_len - 1
+        ));
+    }
+    bricks.shuffle(rng);
+
+    (bricks.join("\n") + "\n").into()
+}
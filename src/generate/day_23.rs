@@ -0,0 +1,70 @@
+/// Hiking trail maps for day 23.
+///
+/// Like the real input, the trails form a lattice of junctions with corridors of random lengths between them, entered
+/// from the start above the top left junction and left to the goal below the bottom right junction. Slopes around the
+/// junctions point right and down.
+use rand::{rngs::StdRng, Rng};
+
+use super::Generated;
+
+/// Most junctions per row and column, so the longest path can still be found by trying every path.
+const MAX_JUNCTIONS: usize = 6;
+
+/// `size` is the number of junctions per row and column, minus one.
+pub fn generate(rng: &mut StdRng, size: usize) -> Generated {
+    let junctions = (size + 1).min(MAX_JUNCTIONS);
+    let lines = |rng: &mut StdRng, first: usize| {
+        let mut lines = vec![first];
+        for _ in 1..junctions {
+            lines.push(lines.last().unwrap() + rng.gen_range(2..=12));
+        }
+        lines
+    };
+    let top = rng.gen_range(2..=6);
+    let rows = lines(rng, top);
+    let cols = lines(rng, 1);
+    let height = rows.last().unwrap() + rng.gen_range(2..=6);
+    let width = cols.last().unwrap() + 2;
+
+    let mut map = vec![vec![b'#'; width]; height];
+    let (first_row, last_row) = (rows[0], *rows.last().unwrap());
+    let (first_col, last_col) = (cols[0], *cols.last().unwrap());
+    for line in &mut map[..=first_row] {
+        line[first_col] = b'.';
+    }
+    for line in &mut map[last_row..] {
+        line[last_col] = b'.';
+    }
+    for &row in &rows {
+        map[row][first_col..=last_col].fill(b'.');
+    }
+    for &col in &cols {
+        for line in &mut map[first_row..=last_row] {
+            line[col] = b'.';
+        }
+    }
+
+    for &row in &rows {
+        for &col in &cols {
+            if map[row - 1][col] == b'.' {
+                map[row - 1][col] = b'v';
+            }
+            if map[row + 1][col] == b'.' {
+                map[row + 1][col] = b'v';
+            }
+            if map[row][col - 1] == b'.' {
+                map[row][col - 1] = b'>';
+            }
+            if map[row][col + 1] == b'.' {
+                map[row][col + 1] = b'>';
+            }
+        }
+    }
+
+    let mut input = String::new();
+    for line in map {
+        input.push_str(std::str::from_utf8(&line).unwrap());
+        input.push('\n');
+    }
+    input.into()
+}
@@ -0,0 +1,54 @@
+/// Hailstones for day 24.
+///
+/// The rock is picked first, and every hailstone is placed so the rock hits it at a distinct time, so part two always
+/// has a solution, the sum of the rock's coordinates. The test area of part one covers the middle of where the hailstones start.
+use rand::{rngs::StdRng, seq::index, Rng};
+
+use super::Generated;
+use crate::template::{answers::Answers, params::Params};
+
+const MAX_POSITION: i64 = 1_000_000;
+const MAX_VELOCITY: i64 = 200;
+
+/// `size` is the number of hailstones, minus four.
+pub fn generate(rng: &mut StdRng, size: usize) -> Generated {
+    let count = size + 4;
+    let mut vector = |max: i64| [(); 3].map(|_| rng.gen_range(-max..=max));
+    let position = vector(MAX_POSITION);
+    let velocity = vector(MAX_VELOCITY);
+
+    // NOTE: the rock hits the hailstone at `time`, so the hailstone starts where its own velocity would take it back.
+    let times = index::sample(rng, count.max(1000) * 10, count);
+    let mut hailstones = vec![];
+    for time in times {
+        let time = time as i64 + 1;
+        let hail_velocity = [(); 3].map(|_| rng.gen_range(-MAX_VELOCITY..=MAX_VELOCITY));
+        let hail_position: [i64; 3] =
+            std::array::from_fn(|i| position[i] + (velocity[i] - hail_velocity[i]) * time);
+        hailstones.push((hail_position, hail_velocity));
+    }
+
+    let (low, high) = hailstones
+        .iter()
+        .flat_map(|(position, _)| [position[0], position[1]])
+        .fold((i64::MAX, i64::MIN), |(low, high), x| {
+            (low.min(x), high.max(x))
+        });
+    let margin = (high - low) / 4;
+
+    let mut input = String::new();
+    for ([x, y, z], [dx, dy, dz]) in hailstones {
+        input.push_str(&format!("{x}, {y}, {z} @ {dx}, {dy}, {dz}\n"));
+    }
+    Generated {
+        input,
+        params: Params::from([
+            ("test_area_min", (low + margin).to_string().as_str()),
+            ("test_area_max", (high - margin).to_string().as_str()),
+        ]),
+        answers: Answers {
+            part_1: None,
+            part_2: Some(position.iter().sum::<i64>().to_string()),
+        },
+    }
+}
@@ -0,0 +1,81 @@
+/// Component wiring diagrams for day 25.
+///
+/// Two groups of components are each wired densely enough that cutting them takes at least four wires, and exactly
+/// three wires join the groups, so there is a single way to disconnect three wires and split the components in two,
+/// and the answer of part one is the product of the sizes of the groups.
+use std::collections::HashSet;
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+
+use super::Generated;
+use crate::template::answers::Answers;
+
+/// `size` is about the number of components of each group, minus four.
+pub fn generate(rng: &mut StdRng, size: usize) -> Generated {
+    let groups = [(); 2].map(|_| size + 4 + rng.gen_range(0..=size));
+    let mut edges = HashSet::new();
+    let mut offset = 0;
+    for group in groups {
+        // NOTE: linking each component to the next two around a circle takes four cuts to split.
+        for i in 0..group {
+            for step in 1..=2 {
+                edges.insert(edge(offset + i, offset + (i + step) % group));
+            }
+        }
+        for _ in 0..group / 2 {
+            let (a, b) = (rng.gen_range(0..group), rng.gen_range(0..group));
+            if a != b {
+                edges.insert(edge(offset + a, offset + b));
+            }
+        }
+        offset += group;
+    }
+    let mut bridges = HashSet::new();
+    while bridges.len() < 3 {
+        bridges.insert(edge(
+            rng.gen_range(0..groups[0]),
+            groups[0] + rng.gen_range(0..groups[1]),
+        ));
+    }
+    edges.extend(bridges);
+
+    let mut taken = HashSet::new();
+    let names: Vec<String> = std::iter::from_fn(|| {
+        Some(
+            (0..3)
+                .map(|_| char::from(rng.gen_range(b'a'..=b'z')))
+                .collect::<String>(),
+        )
+    })
+    .filter(|name| taken.insert(name.clone()))
+    .take(offset)
+    .collect();
+
+    // NOTE: like the real input, each wire is listed once, on the line of either of its components.
+    let mut wires = vec![vec![]; offset];
+    let mut edges: Vec<(usize, usize)> = edges.into_iter().collect();
+    edges.sort_unstable();
+    for (a, b) in edges {
+        let (from, to) = if rng.gen_bool(0.5) { (a, b) } else { (b, a) };
+        wires[from].push(names[to].as_str());
+    }
+    let mut lines: Vec<String> = wires
+        .into_iter()
+        .enumerate()
+        .filter(|(_, to)| !to.is_empty())
+        .map(|(from, to)| format!("{}: {}", names[from], to.join(" ")))
+        .collect();
+    lines.shuffle(rng);
+
+    Generated {
+        answers: Answers {
+            part_1: Some((groups[0] * groups[1]).to_string()),
+            part_2: None,
+        },
+        ..(lines.join("\n") + "\n").into()
+    }
+}
+
+fn edge(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
@@ -0,0 +1,118 @@
+/// Random valid puzzle inputs, to test solutions beyond the examples and our own inputs.
+///
+/// Every generator builds an input of the given size from a seeded random number generator, so the same size and
+/// seed always give the same input. Generated inputs follow the same structure as the real inputs, including the
+/// properties solutions rely on, e.g. the clear paths through the garden on day 21.
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::template::{answers::Answers, params::Params, Day, Year};
+
+mod day_01;
+mod day_02;
+mod day_03;
+mod day_04;
+mod day_05;
+mod day_06;
+mod day_07;
+mod day_08;
+mod day_09;
+mod day_10;
+mod day_11;
+mod day_12;
+mod day_13;
+mod day_14;
+mod day_15;
+mod day_16;
+mod day_17;
+mod day_18;
+mod day_19;
+mod day_20;
+mod day_21;
+mod day_22;
+mod day_23;
+mod day_24;
+mod day_25;
+
+/// A generated input together with the parameters it is solved with, see [`params`](crate::template::params).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Generated {
+    pub input: String,
+    pub params: Params,
+    /// Answers the generator knows by construction, e.g. the rock it placed on day 24.
+    pub answers: Answers,
+}
+
+impl From<String> for Generated {
+    fn from(input: String) -> Self {
+        Generated {
+            input,
+            params: Params::default(),
+            answers: Answers::default(),
+        }
+    }
+}
+
+/// Builds an input of a size of at least `1` from a random number generator. What the size counts depends on the
+/// puzzle, e.g. lines of springs on day 12. Larger sizes give larger inputs.
+pub type Generator = fn(&mut StdRng, usize) -> Generated;
+
+/// The generator for the puzzle of `day` of `year`, if there is one.
+pub fn generator(year: Year, day: Day) -> Option<Generator> {
+    let generator: Generator = match (year.into_inner(), day.into_inner()) {
+        (2023, 1) => day_01::generate,
+        (2023, 2) => day_02::generate,
+        (2023, 3) => day_03::generate,
+        (2023, 4) => day_04::generate,
+        (2023, 5) => day_05::generate,
+        (2023, 6) => day_06::generate,
+        (2023, 7) => day_07::generate,
+        (2023, 8) => day_08::generate,
+        (2023, 9) => day_09::generate,
+        (2023, 10) => day_10::generate,
+        (2023, 11) => day_11::generate,
+        (2023, 12) => day_12::generate,
+        (2023, 13) => day_13::generate,
+        (2023, 14) => day_14::generate,
+        (2023, 15) => day_15::generate,
+        (2023, 16) => day_16::generate,
+        (2023, 17) => day_17::generate,
+        (2023, 18) => day_18::generate,
+        (2023, 19) => day_19::generate,
+        (2023, 20) => day_20::generate,
+        (2023, 21) => day_21::generate,
+        (2023, 22) => day_22::generate,
+        (2023, 23) => day_23::generate,
+        (2023, 24) => day_24::generate,
+        (2023, 25) => day_25::generate,
+        _ => return None,
+    };
+    Some(generator)
+}
+
+/// Generates an input of `size` from `seed`.
+pub fn generate(generator: Generator, size: usize, seed: u64) -> Generated {
+    let mut rng = StdRng::seed_from_u64(seed);
+    generator(&mut rng, size.max(1))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{generate, generator};
+    use crate::{day, template::all_days, year};
+
+    #[test]
+    fn generates_reproducible_inputs() {
+        let generator = generator(year!(2023), day!(12)).unwrap();
+        assert_eq!(generate(generator, 5, 7), generate(generator, 5, 7));
+        assert_ne!(generate(generator, 5, 7), generate(generator, 5, 8));
+        assert!(generate(generator, 0, 7).input.ends_with('\n'));
+    }
+
+    #[test]
+    fn has_generators_for_primary_year() {
+        assert!(all_days().all(|day| generator(year!(2023), day).is_some()));
+        assert!(generator(year!(2015), day!(21)).is_none());
+    }
+}
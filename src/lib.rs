@@ -1,3 +1,4 @@
+pub mod generate;
 pub mod template;
 
 // Use this file to add helper functions and additional modules.
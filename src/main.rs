@@ -1,4 +1,6 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, differential, download, generate, read, scaffold, solve, time, verify,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -7,73 +9,224 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::{
+        cold,
+        commands::{generate, time},
+        complexity, differential,
+        limits::Limits,
+        params::{self, Params},
+        record::OutputFormat,
+        Day, RunOptions, Year,
+    };
     use std::process;
 
     pub enum AppArguments {
         Download {
+            year: Year,
             day: Day,
         },
         Read {
+            year: Year,
             day: Day,
         },
         Scaffold {
+            year: Year,
             day: Day,
             download: bool,
         },
         Solve {
+            year: Year,
             day: Day,
-            release: bool,
             dhat: bool,
             submit: Option<u8>,
+            format: Option<OutputFormat>,
+            options: RunOptions,
+            params: Params,
+            variant: Option<String>,
         },
         All {
-            release: bool,
+            year: Year,
+            options: RunOptions,
         },
         Time {
+            year: Year,
             all: bool,
             day: Option<Day>,
             store: bool,
+            compare: Option<f64>,
+            mode: time::Mode,
+            options: RunOptions,
+        },
+        Verify {
+            year: Year,
+            day: Option<Day>,
+            limits: Limits,
+        },
+        Differential {
+            year: Year,
+            day: Option<Day>,
+            options: differential::Options,
+        },
+        Generate {
+            year: Year,
+            day: Day,
+            size: usize,
+            seed: Option<u64>,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            year: Year,
+        },
+    }
+
+    /// Reads the `--timeout <seconds>` and `--max-rss <MiB>` flags.
+    fn parse_limits(args: &mut pico_args::Arguments) -> Result<Limits, Box<dyn std::error::Error>> {
+        Ok(Limits::new(
+            args.opt_value_from_str("--timeout")?,
+            args.opt_value_from_str("--max-rss")?,
+        )?)
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+        // NOTE: the year applies to every command, solutions of the primary year live directly in `src/bin`.
+        let year = args.opt_value_from_str("--year")?.unwrap_or(Year::PRIMARY);
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
-                release: args.contains("--release"),
+                year,
+                options: RunOptions {
+                    is_release: args.contains("--release"),
+                    threads: args.opt_value_from_str("--threads")?,
+                    limits: parse_limits(&mut args)?,
+                    ..RunOptions::default()
+                },
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let runs: Option<usize> = args.opt_value_from_str("--runs")?;
+                let modes = ["--scale", "--scaling", "--variants", "--cold"];
+                let given: Vec<&str> = modes
+                    .into_iter()
+                    .filter(|flag| args.clone().contains(*flag))
+                    .collect();
+                if given.len() > 1 {
+                    return Err(format!("`{}` can't be combined.", given.join("` and `")).into());
+                }
+                let mode = if args.contains("--scale") {
+                    let default = complexity::Options::default();
+                    time::Mode::Complexity(complexity::Options {
+                        max_size: args
+                            .opt_value_from_str("--size")?
+                            .unwrap_or(default.max_size),
+                        seed: args.opt_value_from_str("--seed")?.unwrap_or(default.seed),
+                        is_plot: args.contains("--plot"),
+                    })
+                } else if args.contains("--scaling") {
+                    time::Mode::Scaling
+                } else if args.contains("--variants") {
+                    time::Mode::Variants
+                } else if args.contains("--cold") {
+                    time::Mode::Cold {
+                        runs: runs.unwrap_or(cold::DEFAULT_RUNS),
+                    }
+                } else {
+                    time::Mode::Warm
+                };
+                let options = RunOptions {
+                    is_alloc: args.contains("--alloc"),
+                    is_counters: args.contains("--counters"),
+                    threads: args.opt_value_from_str("--threads")?,
+                    limits: parse_limits(&mut args)?,
+                    ..RunOptions::default()
+                };
+                let threshold: Option<f64> = args.opt_value_from_str("--threshold")?;
+                let is_compare = args.contains("--compare");
+                if threshold.is_some() && !is_compare {
+                    return Err("`--threshold` only applies to `--compare`.".into());
+                }
+                if let Some(flag) = given.first().filter(|_| store || is_compare) {
+                    return Err(format!(
+                        "`--store` and `--compare` don't apply to `{flag}`, which doesn't store timings."
+                    )
+                    .into());
+                }
+                let compare =
+                    is_compare.then(|| threshold.unwrap_or(time::DEFAULT_COMPARE_THRESHOLD));
 
                 AppArguments::Time {
+                    year,
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    compare,
+                    mode,
+                    options,
+                }
+            }
+            Some("verify") => AppArguments::Verify {
+                year,
+                limits: parse_limits(&mut args)?,
+                day: args.opt_free_from_str()?,
+            },
+            Some("differential") => {
+                let default = differential::Options::default();
+                AppArguments::Differential {
+                    year,
+                    options: differential::Options {
+                        cases: args.opt_value_from_str("--cases")?.unwrap_or(default.cases),
+                        seed: args.opt_value_from_str("--seed")?.unwrap_or(default.seed),
+                        max_size: args
+                            .opt_value_from_str("--size")?
+                            .unwrap_or(default.max_size),
+                    },
+                    day: args.opt_free_from_str()?,
                 }
             }
+            Some("generate") => AppArguments::Generate {
+                year,
+                size: args
+                    .opt_value_from_str("--size")?
+                    .unwrap_or(generate::DEFAULT_SIZE),
+                seed: args.opt_value_from_str("--seed")?,
+                day: args.free_from_str()?,
+            },
             Some("download") => AppArguments::Download {
+                year,
                 day: args.free_from_str()?,
             },
             Some("read") => AppArguments::Read {
+                year,
                 day: args.free_from_str()?,
             },
             Some("scaffold") => AppArguments::Scaffold {
+                year,
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
             },
             Some("solve") => AppArguments::Solve {
+                year,
                 day: args.free_from_str()?,
-                release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                format: args.opt_value_from_str("--format")?,
+                options: RunOptions {
+                    is_release: args.contains("--release"),
+                    threads: args.opt_value_from_str("--threads")?,
+                    limits: parse_limits(&mut args)?,
+                    ..RunOptions::default()
+                },
+                params: args
+                    .values_from_fn("--param", params::parse_pair)?
+                    .into_iter()
+                    .collect(),
+                variant: args.opt_value_from_str("--variant")?,
             },
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("today") => AppArguments::Today { year },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -100,29 +253,64 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download } => {
-                scaffold::handle(day);
+            AppArguments::All { year, options } => all::handle(year, options),
+            AppArguments::Time {
+                year,
+                day,
+                all,
+                store,
+                compare,
+                mode,
+                options,
+            } => time::handle(year, day, all, store, compare, mode, options),
+            AppArguments::Verify { year, day, limits } => verify::handle(year, day, limits),
+            AppArguments::Differential { year, day, options } => {
+                differential::handle(year, day, options)
+            }
+            AppArguments::Generate {
+                year,
+                day,
+                size,
+                seed,
+            } => generate::handle(year, day, size, seed),
+            AppArguments::Download { year, day } => download::handle(year, day),
+            AppArguments::Read { year, day } => read::handle(year, day),
+            AppArguments::Scaffold {
+                year,
+                day,
+                download,
+            } => {
+                scaffold::handle(year, day);
                 if download {
-                    download::handle(day);
+                    download::handle(year, day);
                 }
             }
             AppArguments::Solve {
+                year,
+                day,
+                dhat,
+                submit,
+                format,
+                options,
+                params,
+                variant,
+            } => solve::handle(
+                year,
                 day,
-                release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                format,
+                options,
+                &params,
+                variant.as_deref(),
+            ),
             #[cfg(feature = "today")]
-            AppArguments::Today => {
+            AppArguments::Today { year } => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day);
-                        download::handle(day);
-                        read::handle(day)
+                        scaffold::handle(year, day);
+                        download::handle(year, day);
+                        read::handle(year, day)
                     }
                     None => {
                         eprintln!(
@@ -0,0 +1,150 @@
+/// Runs many solutions in a single process.
+/// The `aggregate` binary links every solution in `src/bin` (see `build.rs`) and hands them to [`run`].
+use std::collections::HashSet;
+use std::env;
+use std::hint::black_box;
+use std::process;
+use std::time::{Duration, Instant};
+
+use crate::template::cold;
+use crate::template::complexity;
+use crate::template::differential;
+use crate::template::inputs::read_inputs;
+use crate::template::limits::ResumeAfter;
+use crate::template::panics;
+use crate::template::record::{OutputFormat, RunSummary};
+use crate::template::solution::Registration;
+use crate::template::threads;
+use crate::template::variants::Selection;
+use crate::template::{Day, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Flags that are followed by a value.
+const VALUE_FLAGS: [&str; 9] = [
+    "--year",
+    "--format",
+    "--threads",
+    "--timeout",
+    "--max-rss",
+    "--cases",
+    "--seed",
+    "--size",
+    "--resume-after",
+];
+
+/// Runs the solutions for every day passed as a free argument, or all solutions if no day was passed.
+/// Understands the same `--time` and `--format` flags as the single day binaries.
+/// Every input of a day is run, except when timing or with `--cold`, where only the default input is run.
+/// Only solutions of the year passed with `--year` are run, defaulting to [`Year::PRIMARY`].
+/// With `--variants`, only solutions that have variants are run, see [`variants`](super::variants).
+/// With `--differential`, the selected solutions are tested against their variants instead, see [`differential`].
+/// With `--complexity`, they are benched over a ladder of input sizes instead, see [`complexity`].
+/// With `--resume-after`, the steps of a day up to a part that was stopped by a limit are skipped, see [`ResumeAfter`].
+pub fn run(solutions: &[Registration]) {
+    threads::configure_from_args();
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let year = args
+        .iter()
+        .position(|x| x == "--year")
+        .and_then(|i| args.get(i + 1)?.parse().ok())
+        .unwrap_or(Year::PRIMARY);
+    // NOTE: skip values of flags, e.g. the `4` of `--threads 4` is not a day.
+    let days: HashSet<Day> = args
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i == 0 || !VALUE_FLAGS.contains(&args[i - 1].as_str()))
+        .filter_map(|(_, x)| x.parse().ok())
+        .collect();
+    let is_timed = args.iter().any(|x| x == "--time");
+    let is_cold = cold::is_enabled();
+    let is_variants = Selection::from_args() == Selection::All;
+    let format = OutputFormat::from_args();
+    let resume = ResumeAfter::from_args();
+
+    let selected: Vec<&Registration> = solutions
+        .iter()
+        .filter(|s| s.year == year && (days.is_empty() || days.contains(&s.day)))
+        .filter(|s| !is_variants || !s.metadata.variants.is_empty())
+        .collect();
+
+    if args.iter().any(|x| x == "--differential") {
+        if !differential::run(&selected, &differential::Options::from_args()) {
+            process::exit(1);
+        }
+        return;
+    }
+
+    if args.iter().any(|x| x == "--complexity") {
+        complexity::run(&selected, &complexity::Options::from_args());
+        return;
+    }
+
+    let timer = Instant::now();
+    let mut need_space = false;
+
+    for entry in &selected {
+        if format == OutputFormat::Human {
+            if need_space {
+                println!();
+            }
+            need_space = true;
+            println!("{ANSI_BOLD}Day {}{ANSI_RESET}", entry.day);
+            println!("------");
+        }
+
+        let mut inputs = read_inputs((entry.year, entry.day));
+        if is_timed || is_cold {
+            inputs.retain(|input| input.name.is_none());
+        }
+
+        if inputs.is_empty() {
+            eprintln!("Could not read input file for day {}.", entry.day);
+        }
+
+        if let Some(resume) = resume.as_ref().filter(|resume| resume.day == entry.day) {
+            let names: Vec<Option<&str>> =
+                inputs.iter().map(|input| input.name.as_deref()).collect();
+            let skipped = resume.skipped_inputs(&names);
+            inputs.drain(..skipped);
+        }
+
+        for input in &inputs {
+            (entry.run)(&input.data, input.name.as_deref());
+        }
+    }
+
+    let wall = if is_timed && !is_variants {
+        // NOTE: benching runs every part many times, so measure the end-to-end time in a separate pass.
+        // this runs after benching, as every part is known to finish within its limits by then.
+        measure_wall_clock(&selected)
+    } else {
+        timer.elapsed()
+    };
+
+    let summary = RunSummary { wall };
+
+    match format {
+        OutputFormat::Human => {
+            let wall_millis = summary.wall.as_secs_f64() * 1000.0;
+            println!(
+                "\n{ANSI_BOLD}Total (Wall):{ANSI_RESET} {ANSI_ITALIC}{wall_millis:.2}ms{ANSI_RESET}"
+            );
+        }
+        OutputFormat::Json => println!("{}", summary.to_json_line()),
+    }
+}
+
+/// Reads the input of and solves every selected day exactly once, back to back.
+/// Days that panic are included up to their panic, which was already reported while running them.
+fn measure_wall_clock(selected: &[&Registration]) -> Duration {
+    let timer = Instant::now();
+    for entry in selected {
+        if let Some(input) = read_inputs((entry.year, entry.day))
+            .into_iter()
+            .find(|input| input.name.is_none())
+        {
+            let _ = black_box(panics::catch(|| (entry.solve)(&input.data)));
+        }
+    }
+    timer.elapsed()
+}
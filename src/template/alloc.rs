@@ -0,0 +1,238 @@
+/// Allocation profiling through a counting global allocator.
+///
+/// The allocator is only installed with the `count-alloc` feature, which `cargo time --alloc` enables. Without it,
+/// [`measure`] records nothing, so regular runs don't pay for the bookkeeping.
+use std::{collections::HashMap, fmt::Display};
+use tinyjson::JsonValue;
+
+/// Heap usage of a single run of a solution part.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations, including reallocations.
+    pub allocations: u64,
+    /// Total number of bytes requested.
+    pub bytes: u64,
+    /// Highest number of bytes in use at any point, on top of what was in use before the run.
+    pub peak_bytes: u64,
+}
+
+/// Whether allocations should be counted, i.e. the `--alloc` flag was passed and the allocator is installed.
+pub fn is_enabled() -> bool {
+    cfg!(feature = "count-alloc") && std::env::args().any(|x| x == "--alloc")
+}
+
+/// Runs `func` once and counts its allocations, see [`is_enabled`].
+pub fn measure<T>(func: impl FnOnce() -> T) -> Option<AllocStats> {
+    if !is_enabled() {
+        return None;
+    }
+
+    #[cfg(feature = "count-alloc")]
+    {
+        let start = counting::snapshot();
+        std::hint::black_box(func());
+        Some(counting::since(&start))
+    }
+
+    #[cfg(not(feature = "count-alloc"))]
+    {
+        drop(func);
+        None
+    }
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `3.4 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocations · {} allocated · {} peak",
+            self.allocations,
+            format_bytes(self.bytes),
+            format_bytes(self.peak_bytes)
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "count-alloc")]
+pub use counting::CountingAlloc;
+
+#[cfg(feature = "count-alloc")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use super::AllocStats;
+
+    static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+    static BYTES: AtomicU64 = AtomicU64::new(0);
+    static CURRENT: AtomicU64 = AtomicU64::new(0);
+    static PEAK: AtomicU64 = AtomicU64::new(0);
+
+    /// Wraps the system allocator and counts every allocation.
+    pub struct CountingAlloc;
+
+    fn record(size: usize) {
+        let size = size as u64;
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size, Ordering::Relaxed);
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+
+    unsafe impl GlobalAlloc for CountingAlloc {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            record(layout.size());
+            System.alloc(layout)
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            record(layout.size());
+            System.alloc_zeroed(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            CURRENT.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+            System.dealloc(ptr, layout);
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            CURRENT.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+            record(new_size);
+            System.realloc(ptr, layout, new_size)
+        }
+    }
+
+    pub(super) struct Snapshot {
+        allocations: u64,
+        bytes: u64,
+        current: u64,
+    }
+
+    /// Captures the counters and resets the peak to the heap currently in use.
+    pub(super) fn snapshot() -> Snapshot {
+        let current = CURRENT.load(Ordering::Relaxed);
+        PEAK.store(current, Ordering::Relaxed);
+        Snapshot {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            bytes: BYTES.load(Ordering::Relaxed),
+            current,
+        }
+    }
+
+    pub(super) fn since(start: &Snapshot) -> AllocStats {
+        AllocStats {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed) - start.allocations,
+            bytes: BYTES.load(Ordering::Relaxed) - start.bytes,
+            peak_bytes: PEAK.load(Ordering::Relaxed).saturating_sub(start.current),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+const KEYS: [&str; 3] = ["allocations", "alloc_bytes", "peak_bytes"];
+
+impl AllocStats {
+    /// Writes the statistics as flat keys into a JSON object.
+    pub(crate) fn insert_into(&self, map: &mut HashMap<String, JsonValue>) {
+        let values = [self.allocations, self.bytes, self.peak_bytes];
+
+        #[allow(clippy::cast_precision_loss)]
+        for (key, value) in KEYS.iter().zip(values) {
+            map.insert((*key).into(), JsonValue::Number(value as f64));
+        }
+    }
+
+    /// Reads statistics written by [`AllocStats::insert_into`] from a JSON object.
+    /// Objects without allocation counts yield `None`.
+    pub(crate) fn read_from(map: &HashMap<String, JsonValue>) -> Result<Option<Self>, String> {
+        if !map.contains_key(KEYS[0]) {
+            return Ok(None);
+        }
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| {
+            map.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|x| *x as u64)
+                .ok_or(format!("Expected `{key}` to be a number."))
+        };
+
+        Ok(Some(Self {
+            allocations: number(KEYS[0])?,
+            bytes: number(KEYS[1])?,
+            peak_bytes: number(KEYS[2])?,
+        }))
+    }
+}
+
+impl From<&AllocStats> for JsonValue {
+    fn from(value: &AllocStats) -> Self {
+        let mut map = HashMap::new();
+        value.insert_into(&mut map);
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for AllocStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected allocation stats to be a JSON object.")?;
+        AllocStats::read_from(json)?.ok_or("Expected allocation stats to have allocations.".into())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{format_bytes, AllocStats};
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(3482), "3.4 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MiB");
+    }
+
+    #[test]
+    fn roundtrips_alloc_stats() {
+        let stats = AllocStats {
+            allocations: 12,
+            bytes: 4096,
+            peak_bytes: 1024,
+        };
+        let mut map = HashMap::new();
+        stats.insert_into(&mut map);
+        assert_eq!(AllocStats::read_from(&map), Ok(Some(stats)));
+        assert_eq!(AllocStats::read_from(&HashMap::new()), Ok(None));
+    }
+}
@@ -0,0 +1,199 @@
+/// Known-good answers of solutions, stored in `answers/NN.txt` in the data directory of a year.
+/// Answers of named inputs (see [`inputs`](super::inputs)) are stored in `answers/NN/<name>.txt`.
+///
+/// Every line of an answers file holds the accepted answer of one part, prefixed with the part number:
+///
+/// ```text
+/// 1: 4361
+/// 2: 467835
+/// ```
+use std::{fmt::Display, fs, io::ErrorKind, path::Path, str::FromStr};
+
+use crate::template::{Day, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// The accepted answers of a single day. Parts without a recorded answer are `None`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// How an answer relates to the recorded answer of its part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AnswerStatus {
+    Correct,
+    /// Holds the recorded answer.
+    Incorrect(String),
+    /// No answer was recorded for the part.
+    Unknown,
+}
+
+impl Answers {
+    /// Reads the answers of a day for the default input, or the input called `input`.
+    /// If there is no answers file, no answers are known.
+    pub fn read(year: Year, day: Day, input: Option<&str>) -> Result<Self, String> {
+        let dir = year.data_dir().join("answers");
+        let path = match input {
+            Some(name) => dir.join(day.to_string()).join(format!("{name}.txt")),
+            None => dir.join(format!("{day}.txt")),
+        };
+
+        Self::read_from(&path)
+    }
+
+    /// Reads answers from `path`. If the file doesn't exist, no answers are known.
+    pub fn read_from(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(s) => s
+                .parse()
+                .map_err(|e| format!("Invalid answers file {}: {e}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Reads the answers of every input of a day that has an answers file: the default input first, followed by named
+    /// inputs ordered by name.
+    pub fn read_all(year: Year, day: Day) -> Result<Vec<(Option<String>, Self)>, String> {
+        Self::read_all_from(&year.data_dir().join("answers"), day)
+    }
+
+    fn read_all_from(dir: &Path, day: Day) -> Result<Vec<(Option<String>, Self)>, String> {
+        let default = dir.join(format!("{day}.txt"));
+        let mut all = vec![];
+        if default.exists() {
+            all.push((None, Self::read_from(&default)?));
+        }
+
+        let mut named: Vec<_> = fs::read_dir(dir.join(day.to_string()))
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                if path.extension()? != "txt" {
+                    return None;
+                }
+                Some((path.file_stem()?.to_str()?.to_string(), path))
+            })
+            .collect();
+        named.sort_unstable();
+
+        for (name, path) in named {
+            all.push((Some(name), Self::read_from(&path)?));
+        }
+        Ok(all)
+    }
+
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Checks an answer against the recorded answer of `part`. A missing answer is only correct if none is expected.
+    pub fn check(&self, part: u8, answer: Option<&str>) -> AnswerStatus {
+        match self.get(part) {
+            None => AnswerStatus::Unknown,
+            Some(expected) if answer.map(str::trim) == Some(expected) => AnswerStatus::Correct,
+            Some(expected) => AnswerStatus::Incorrect(expected.to_string()),
+        }
+    }
+}
+
+impl FromStr for Answers {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = Answers::default();
+
+        for line in s.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let (part, answer) = line
+                .split_once(':')
+                .ok_or(format!("expected `<part>: <answer>`, found `{line}`."))?;
+
+            let slot = match part.trim() {
+                "1" => &mut answers.part_1,
+                "2" => &mut answers.part_2,
+                x => return Err(format!("expected part to be 1 or 2, found `{x}`.")),
+            };
+
+            *slot = Some(answer.trim().to_string());
+        }
+
+        Ok(answers)
+    }
+}
+
+impl Display for AnswerStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnswerStatus::Correct => write!(f, "{ANSI_ITALIC}correct{ANSI_RESET}"),
+            AnswerStatus::Incorrect(expected) => {
+                write!(f, "{ANSI_BOLD}incorrect{ANSI_RESET} (expected {expected})")
+            }
+            AnswerStatus::Unknown => write!(f, "{ANSI_ITALIC}unknown{ANSI_RESET}"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::{AnswerStatus, Answers};
+    use crate::day;
+
+    #[test]
+    fn parses_answers() {
+        let answers: Answers = "1: 4361\n\n 2 : 467835 \n".parse().unwrap();
+        assert_eq!(answers.get(1), Some("4361"));
+        assert_eq!(answers.get(2), Some("467835"));
+    }
+
+    #[test]
+    fn parses_partial_answers() {
+        let answers: Answers = "2: abc".parse().unwrap();
+        assert_eq!(answers.part_1, None);
+        assert_eq!(answers.get(2), Some("abc"));
+    }
+
+    #[test]
+    fn rejects_invalid_answers() {
+        assert!("4361".parse::<Answers>().is_err());
+        assert!("3: 4361".parse::<Answers>().is_err());
+    }
+
+    #[test]
+    fn checks_answers() {
+        let answers: Answers = "1: 42".parse().unwrap();
+        assert_eq!(answers.check(1, Some("42")), AnswerStatus::Correct);
+        assert_eq!(
+            answers.check(1, Some("41")),
+            AnswerStatus::Incorrect("42".into())
+        );
+        assert_eq!(answers.check(1, None), AnswerStatus::Incorrect("42".into()));
+        assert_eq!(answers.check(2, Some("1")), AnswerStatus::Unknown);
+    }
+
+    #[test]
+    fn reads_answers_of_all_inputs() {
+        let dir = env::temp_dir().join(format!("aoc-answers-{}", process::id()));
+        fs::create_dir_all(dir.join("03")).unwrap();
+        fs::write(dir.join("03.txt"), "1: 4361").unwrap();
+        fs::write(dir.join("03").join("bob.txt"), "2: 7").unwrap();
+        fs::write(dir.join("03").join("alice.txt"), "1: 5").unwrap();
+        fs::write(dir.join("03").join("notes.md"), "not answers").unwrap();
+
+        let all = Answers::read_all_from(&dir, day!(3)).unwrap();
+        let inputs: Vec<_> = all.iter().map(|(name, _)| name.as_deref()).collect();
+        assert_eq!(inputs, [None, Some("alice"), Some("bob")]);
+        assert_eq!(all[2].1.get(2), Some("7"));
+        assert!(Answers::read_all_from(&dir, day!(4)).unwrap().is_empty());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
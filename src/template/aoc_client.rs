@@ -0,0 +1,434 @@
+/// Minimal HTTP client for adventofcode.com, used to download inputs and puzzles and to submit answers.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use crate::template::{Day, Year};
+
+const BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    /// The server responded with an unexpected status code.
+    Status(u16),
+    /// The request could not be sent, e.g. because the server is unreachable.
+    Transport(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Set AOC_SESSION or write it to ~/.adventofcode.session."
+            ),
+            AocClientError::Status(400) => {
+                write!(
+                    f,
+                    "the server rejected the request, is the session cookie valid?"
+                )
+            }
+            AocClientError::Status(404) => {
+                write!(
+                    f,
+                    "the server could not find the puzzle, is it unlocked yet?"
+                )
+            }
+            AocClientError::Status(code) => {
+                write!(f, "the server responded with status {code}.")
+            }
+            AocClientError::Transport(e) => write!(f, "{e}"),
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(code, _) => AocClientError::Status(code),
+            ureq::Error::Transport(e) => AocClientError::Transport(e.to_string()),
+        }
+    }
+}
+
+/// What the server made of a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction.
+    Incorrect,
+    /// An answer was submitted too recently.
+    Wait,
+    /// The part was already solved, or is not unlocked yet.
+    WrongLevel,
+    Unknown,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SubmissionResponse {
+    pub outcome: SubmissionOutcome,
+    /// The message of the response page, as plain text.
+    pub message: String,
+}
+
+pub struct AocClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    year: u16,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str, year: u16) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            year,
+        }
+    }
+
+    /// Creates a client for the puzzles of `year` on adventofcode.com.
+    /// The session cookie is read from the `AOC_SESSION` environment variable or, if unset, from `~/.adventofcode.session`.
+    pub fn from_env(year: Year) -> Result<Self, AocClientError> {
+        let session = match env::var("AOC_SESSION") {
+            Ok(session) if !session.trim().is_empty() => session,
+            _ => session_file_path()
+                .and_then(|path| fs::read_to_string(path).ok())
+                .filter(|session| !session.trim().is_empty())
+                .ok_or(AocClientError::SessionNotFound)?,
+        };
+
+        Ok(Self::new(BASE_URL, &session, year.into_inner()))
+    }
+
+    /// Fetches the puzzle input of a day.
+    pub fn input(&self, day: Day) -> Result<String, AocClientError> {
+        let url = format!("{}/input", self.day_url(day));
+        self.get(&url)
+    }
+
+    /// Fetches the description of a day as markdown. Contains part two once part one is solved.
+    pub fn puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        let html = self.get(&self.day_url(day))?;
+        let articles: Vec<String> = extract_articles(&html)
+            .into_iter()
+            .map(html_to_markdown)
+            .collect();
+        Ok(articles.join("\n\n"))
+    }
+
+    pub fn submit(
+        &self,
+        day: Day,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmissionResponse, AocClientError> {
+        let url = format!("{}/answer", self.day_url(day));
+        let html = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?
+            .into_string()?;
+
+        let message = extract_articles(&html)
+            .into_iter()
+            .map(html_to_markdown)
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        Ok(SubmissionResponse {
+            outcome: SubmissionOutcome::from_message(&message),
+            message,
+        })
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day.into_inner())
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        let response = self.agent.get(url).set("Cookie", &self.cookie()).call()?;
+        Ok(response.into_string()?)
+    }
+}
+
+impl SubmissionOutcome {
+    fn from_message(message: &str) -> Self {
+        // NOTE: a wrong answer also asks to wait a bit, so check for the direction first.
+        if message.contains("That's the right answer") {
+            SubmissionOutcome::Correct
+        } else if message.contains("your answer is too high") {
+            SubmissionOutcome::TooHigh
+        } else if message.contains("your answer is too low") {
+            SubmissionOutcome::TooLow
+        } else if message.contains("That's not the right answer") {
+            SubmissionOutcome::Incorrect
+        } else if message.contains("You gave an answer too recently") {
+            SubmissionOutcome::Wait
+        } else if message.contains("You don't seem to be solving the right level") {
+            SubmissionOutcome::WrongLevel
+        } else {
+            SubmissionOutcome::Unknown
+        }
+    }
+}
+
+impl Display for SubmissionOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SubmissionOutcome::Correct => "correct",
+            SubmissionOutcome::TooHigh => "too high",
+            SubmissionOutcome::TooLow => "too low",
+            SubmissionOutcome::Incorrect => "incorrect",
+            SubmissionOutcome::Wait => "wait",
+            SubmissionOutcome::WrongLevel => "wrong level",
+            SubmissionOutcome::Unknown => "unknown",
+        })
+    }
+}
+
+impl FromStr for SubmissionOutcome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(SubmissionOutcome::Correct),
+            "too high" => Ok(SubmissionOutcome::TooHigh),
+            "too low" => Ok(SubmissionOutcome::TooLow),
+            "incorrect" => Ok(SubmissionOutcome::Incorrect),
+            "wait" => Ok(SubmissionOutcome::Wait),
+            "wrong level" => Ok(SubmissionOutcome::WrongLevel),
+            "unknown" => Ok(SubmissionOutcome::Unknown),
+            x => Err(format!("unknown submission outcome `{x}`.")),
+        }
+    }
+}
+
+fn session_file_path() -> Option<PathBuf> {
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+    Some(Path::new(&home).join(".adventofcode.session"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Returns the inner html of every `<article>` element.
+fn extract_articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(content_start) = rest[start..].find('>').map(|i| start + i + 1) else {
+            break;
+        };
+        let Some(end) = rest[content_start..].find("</article>") else {
+            break;
+        };
+        articles.push(&rest[content_start..content_start + end]);
+        rest = &rest[content_start + end..];
+    }
+
+    articles
+}
+
+/// Converts the small subset of html used in puzzle descriptions to markdown.
+fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+    let mut in_pre = false;
+
+    while let Some(start) = rest.find('<') {
+        out.push_str(&decode_entities(&rest[..start]));
+
+        let Some(end) = rest[start..].find('>').map(|i| start + i) else {
+            break;
+        };
+        let tag = &rest[start + 1..end];
+        let name = tag
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+
+        match name.as_str() {
+            "h2" => out.push_str("## "),
+            "/h2" | "/p" | "/ul" => out.push_str("\n\n"),
+            "pre" => {
+                in_pre = true;
+                out.push_str("```\n");
+            }
+            "/pre" => {
+                in_pre = false;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            "code" | "/code" if !in_pre => out.push('`'),
+            "em" | "/em" if !in_pre => out.push('*'),
+            "li" => out.push_str("- "),
+            "/li" => out.push('\n'),
+            _ => {}
+        }
+
+        rest = &rest[end + 1..];
+    }
+
+    out.push_str(&decode_entities(rest));
+
+    while out.contains("\n\n\n") {
+        out = out.replace("\n\n\n", "\n\n");
+    }
+
+    out.trim().to_string()
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    use super::{html_to_markdown, AocClient, AocClientError, SubmissionOutcome};
+    use crate::day;
+
+    /// Serves a single request with the given status and body. Returns the base url and the received request.
+    fn serve(status: &'static str, body: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(x) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = x.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut body_buf = vec![0; content_length];
+            reader.read_exact(&mut body_buf).unwrap();
+            request.push_str(&String::from_utf8(body_buf).unwrap());
+
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+
+            request
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn fetches_inputs() {
+        let (base_url, server) = serve("200 OK", "1\n2\n3\n");
+        let client = AocClient::new(&base_url, "abc\n", 2023);
+        assert_eq!(client.input(day!(7)).unwrap(), "1\n2\n3\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2023/day/7/input HTTP/1.1"));
+        assert!(request.contains("Cookie: session=abc\r\n"));
+    }
+
+    #[test]
+    fn fetches_puzzles() {
+        let (base_url, server) = serve(
+            "200 OK",
+            "<main><article class=\"day-desc\"><h2>--- Day 1: Trebuchet?! ---</h2><p>Some <em>text</em> &amp; <code>code</code>.</p></article></main>",
+        );
+        let client = AocClient::new(&base_url, "abc", 2023);
+        assert_eq!(
+            client.puzzle(day!(1)).unwrap(),
+            "## --- Day 1: Trebuchet?! ---\n\nSome *text* & `code`."
+        );
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, server) = serve(
+            "200 OK",
+            "<article><p>That's not the right answer; your answer is too high.  Please wait one minute before trying again.</p></article>",
+        );
+        let client = AocClient::new(&base_url, "abc", 2023);
+        let response = client.submit(day!(12), 2, "42").unwrap();
+        assert_eq!(response.outcome, SubmissionOutcome::TooHigh);
+        assert!(response.message.starts_with("That's not the right answer"));
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2023/day/12/answer HTTP/1.1"));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn reports_bad_status() {
+        let (base_url, server) = serve("404 Not Found", "");
+        let client = AocClient::new(&base_url, "abc", 2023);
+        assert!(matches!(
+            client.input(day!(25)),
+            Err(AocClientError::Status(404))
+        ));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn converts_code_blocks() {
+        assert_eq!(
+            html_to_markdown("<p>Example:</p>\n<pre><code>1 &lt; 2\n<em>3</em>\n</code></pre>\n<ul><li>a</li><li>b</li></ul>"),
+            "Example:\n\n```\n1 < 2\n3\n```\n\n- a\n- b"
+        );
+    }
+}
@@ -0,0 +1,233 @@
+/// Cold-start benchmarks: every run is a fresh process with evicted caches, like a real one-shot run.
+///
+/// Warm benchmarks (see [`runner`](super::runner)) amortize one-off costs like page faults, cold caches and starting
+/// rayon's thread pool over thousands of samples. Cold runs measure the first call of every part instead.
+use std::{
+    collections::{BTreeMap, HashMap},
+    env,
+    hint::black_box,
+    io,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    time::{Duration, Instant},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    all_days,
+    record::{part_label, PartRecord, RunSummary, PARSE_PART},
+    runner::{print_result, print_stats},
+    stats::Stats,
+    Day, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+
+/// Number of fresh processes per day if `--runs` isn't passed.
+pub const DEFAULT_RUNS: usize = 10;
+
+/// Size of the buffer written by [`evict_caches`], larger than the last level cache of common CPUs.
+const EVICTION_BUFFER_BYTES: usize = 64 * 1024 * 1024;
+
+/// Whether the process is a cold run, i.e. the `--cold` flag was passed. Cold runs only run the default input.
+pub fn is_enabled() -> bool {
+    env::args().any(|x| x == "--cold")
+}
+
+/// Evicts the CPU caches by writing a buffer larger than them, so previous runs don't leave data behind.
+/// The page cache of the OS is not dropped, as that needs elevated privileges.
+fn evict_caches() {
+    let mut buffer = vec![0u8; EVICTION_BUFFER_BYTES];
+    for (i, byte) in buffer.iter_mut().enumerate().step_by(64) {
+        #[allow(clippy::cast_possible_truncation)]
+        {
+            *byte = i as u8;
+        }
+    }
+    black_box(&buffer);
+}
+
+/// Everything measured for one day over all cold runs.
+#[derive(Debug, Default)]
+struct DayRuns {
+    /// First call durations and the last answer of every part, by part.
+    parts: BTreeMap<u8, (Vec<Duration>, Option<String>)>,
+    /// Time from spawning the process to its exit.
+    process: Vec<Duration>,
+}
+
+/// Runs `days` of `year` `runs` times each in a fresh process and prints the distribution of their first calls.
+/// Afterwards, all days are run together in a fresh process `runs` times, as `cargo all` would.
+pub fn run_cold(year: Year, days: &[Day], runs: usize) {
+    let days: Vec<Day> = all_days().filter(|day| days.contains(day)).collect();
+    let runs = runs.max(1);
+
+    let binary = match build_aggregate() {
+        Ok(binary) => binary,
+        Err(e) => {
+            eprintln!("Failed to build solutions: {e}");
+            return;
+        }
+    };
+
+    let mut process_medians = Duration::ZERO;
+
+    for (i, day) in days.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        let mut day_runs = DayRuns::default();
+
+        for _ in 0..runs {
+            match run_once(&binary, year, &[*day]) {
+                Ok((records, _, process)) => {
+                    for record in records {
+                        let (durations, answer) = day_runs.parts.entry(record.part).or_default();
+                        durations.push(record.stats.mean);
+                        *answer = record.answer;
+                    }
+                    day_runs.process.push(process);
+                }
+                Err(e) => {
+                    eprintln!("Failed to run day {day}: {e}");
+                    break;
+                }
+            }
+        }
+
+        if day_runs.parts.is_empty() {
+            println!("Not solved.");
+            continue;
+        }
+
+        for (part, (durations, answer)) in &mut day_runs.parts {
+            let stats = Stats::from_samples(durations);
+            let duration_str = format_cold(&stats);
+            if *part == PARSE_PART {
+                println!("{}:{duration_str}", part_label(*part));
+            } else {
+                print_result(answer, &part_label(*part), &duration_str);
+            }
+            print_stats(&stats);
+        }
+
+        let process = Stats::from_samples(&mut day_runs.process);
+        process_medians += process.median;
+        println!(
+            "Process: {ANSI_ITALIC}{:.1?}{ANSI_RESET} (median, including process startup)",
+            process.median
+        );
+    }
+
+    let mut one_shot: Vec<Duration> = vec![];
+    let mut in_process: Vec<Duration> = vec![];
+
+    for _ in 0..runs {
+        match run_once(&binary, year, &days) {
+            Ok((_, summary, process)) => {
+                one_shot.push(process);
+                if let Some(summary) = summary {
+                    in_process.push(summary.wall);
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to run all days: {e}");
+                break;
+            }
+        }
+    }
+
+    println!(
+        "\n{ANSI_BOLD}Total (Cold, per day):{ANSI_RESET} {ANSI_ITALIC}{:.2}ms{ANSI_RESET}",
+        process_medians.as_secs_f64() * 1000.0
+    );
+
+    if !one_shot.is_empty() {
+        let one_shot = Stats::from_samples(&mut one_shot);
+        println!(
+            "{ANSI_BOLD}Total (Cold, one-shot):{ANSI_RESET} {ANSI_ITALIC}{:.2}ms{ANSI_RESET}",
+            one_shot.median.as_secs_f64() * 1000.0
+        );
+    }
+
+    if !in_process.is_empty() {
+        let in_process = Stats::from_samples(&mut in_process);
+        println!(
+            "{ANSI_BOLD}Total (Cold, in process):{ANSI_RESET} {ANSI_ITALIC}{:.2}ms{ANSI_RESET}",
+            in_process.median.as_secs_f64() * 1000.0
+        );
+    }
+}
+
+fn format_cold(stats: &Stats) -> String {
+    format!(" ({:.1?} cold @ {} runs)", stats.median, stats.samples)
+}
+
+/// Builds an optimized `aggregate` binary and returns its path. Running it directly keeps cargo out of the timings.
+/// The path is taken from the artifact cargo reports, so it respects the target directory of any configuration.
+fn build_aggregate() -> io::Result<PathBuf> {
+    let output = Command::new("cargo")
+        .args(["build", "--quiet", "--release", "--bin", "aggregate"])
+        .args(["--message-format", "json"])
+        .stderr(Stdio::inherit())
+        .output()?;
+
+    if !output.status.success() {
+        return Err(io::Error::other("cargo build failed"));
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(executable)
+        .ok_or_else(|| io::Error::other("cargo build did not report the aggregate binary"))
+}
+
+/// Path of the `aggregate` binary if `message` is cargo's `compiler-artifact` message about it.
+fn executable(message: &str) -> Option<PathBuf> {
+    let json: JsonValue = message.parse().ok()?;
+    let message: &HashMap<String, JsonValue> = json.get()?;
+    let field = |key: &str| message.get(key)?.get::<String>();
+    let target: &HashMap<String, JsonValue> = message.get("target")?.get()?;
+    let name: &String = target.get("name")?.get()?;
+
+    if field("reason")? != "compiler-artifact" || name != "aggregate" {
+        return None;
+    }
+    field("executable").map(PathBuf::from)
+}
+
+/// Evicts the caches and runs `days` in a fresh process.
+/// Returns their records, the summary and the time from spawning the process until it exited.
+fn run_once(
+    binary: &Path,
+    year: Year,
+    days: &[Day],
+) -> io::Result<(Vec<PartRecord>, Option<RunSummary>, Duration)> {
+    let mut args = ["--format", "json", "--cold"].map(String::from).to_vec();
+    args.extend(["--year".to_string(), year.to_string()]);
+    args.extend(days.iter().map(Day::to_string));
+
+    evict_caches();
+
+    let timer = Instant::now();
+    let output = Command::new(binary)
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()?;
+    let process = timer.elapsed();
+
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "aggregate failed with {}",
+            output.status
+        )));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let records = stdout.lines().filter_map(|l| l.parse().ok()).collect();
+    let summary = stdout.lines().find_map(|l| l.parse().ok());
+
+    Ok((records, summary, process))
+}
@@ -1,5 +1,10 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::process;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{all_days, run_multi::run_multi, RunOptions, Year};
+
+/// Runs every day of `year`. Exits with a non-zero status if the solutions couldn't be run or any part failed.
+pub fn handle(year: Year, options: RunOptions) {
+    if !run_multi(year, &all_days().collect(), &options).is_ok() {
+        process::exit(1);
+    }
 }
@@ -0,0 +1,42 @@
+use std::process::{self, Command, Stdio};
+
+use crate::template::{differential::Options, Day, Year};
+
+/// Tests optimized builds of the solutions of `year` against their variants on generated inputs, see
+/// [`differential`](crate::template::differential). Only `day` is tested if it is passed.
+/// Exits with a non-zero status if any part disagrees with one of its variants.
+pub fn handle(year: Year, day: Option<Day>, options: Options) {
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--release".to_string(),
+        "--bin".to_string(),
+        "aggregate".to_string(),
+        "--".to_string(),
+        "--differential".to_string(),
+        "--year".to_string(),
+        year.to_string(),
+        "--cases".to_string(),
+        options.cases.to_string(),
+        "--seed".to_string(),
+        options.seed.to_string(),
+        "--size".to_string(),
+        options.max_size.to_string(),
+    ];
+
+    if let Some(day) = day {
+        cmd_args.push(day.to_string());
+    }
+
+    let mut cmd = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    let status = cmd.wait().unwrap();
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
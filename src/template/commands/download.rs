@@ -1,14 +1,45 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use std::{fs, process};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+use crate::template::{aoc_client::AocClient, Day, Year};
+
+pub fn handle(year: Year, day: Day) {
+    let client = match AocClient::from_env(year) {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("failed to set up client: {e}");
+            process::exit(1);
+        }
+    };
+
+    let data_dir = year.data_dir();
+    let input_path = data_dir.join("inputs").join(format!("{day}.txt"));
+    let puzzle_path = data_dir.join("puzzles").join(format!("{day}.md"));
+
+    if let Err(e) = fs::create_dir_all(data_dir.join("inputs"))
+        .and_then(|()| fs::create_dir_all(data_dir.join("puzzles")))
+    {
+        eprintln!("failed to create data directories: {e}");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    let result = client
+        .input(day)
+        .and_then(|input| Ok(fs::write(&input_path, input)?))
+        .and_then(|()| client.puzzle(day))
+        .and_then(|puzzle| Ok(fs::write(&puzzle_path, puzzle)?));
+
+    if let Err(e) = result {
+        eprintln!("failed to download day {day}: {e}");
         process::exit(1);
-    };
+    }
+
+    println!("---");
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        input_path.display()
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
 }
@@ -0,0 +1,41 @@
+use std::process;
+
+use crate::{
+    generate::{self, Generated},
+    template::{Day, Year},
+};
+
+/// The size of generated inputs if none is passed, small enough to read.
+pub const DEFAULT_SIZE: usize = 10;
+
+/// Prints an input for the puzzle of `day` generated from `seed`, or a random seed, see
+/// [`generate`](crate::generate). The seed, the parameters to solve the input with and the answers the generator knows
+/// go to stderr, so the input can be redirected to a file.
+pub fn handle(year: Year, day: Day, size: usize, seed: Option<u64>) {
+    let Some(generator) = generate::generator(year, day) else {
+        eprintln!("no generator for day {day} of {year}");
+        process::exit(1);
+    };
+
+    let seed = seed.unwrap_or_else(rand::random);
+    let Generated {
+        input,
+        params,
+        answers,
+    } = generate::generate(generator, size, seed);
+
+    eprintln!("Generated day {day} with --size {size} --seed {seed}");
+    if !params.is_empty() {
+        let hints: Vec<String> = params
+            .iter()
+            .map(|(name, value)| format!("--param {name}={value}"))
+            .collect();
+        eprintln!("Solve it with {}", hints.join(" "));
+    }
+    for part in [1, 2] {
+        if let Some(answer) = answers.get(part) {
+            eprintln!("Part {part} answers {answer}");
+        }
+    }
+    print!("{input}");
+}
@@ -1,6 +1,9 @@
 pub mod all;
+pub mod differential;
 pub mod download;
+pub mod generate;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
 pub mod time;
+pub mod verify;
@@ -1,15 +1,29 @@
-use std::process;
+use std::{fs, process};
 
-use crate::template::{aoc_cli, Day};
+use crate::template::{aoc_client::AocClient, Day, Year};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+pub fn handle(year: Year, day: Day) {
+    let client = match AocClient::from_env(year) {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("failed to set up client: {e}");
+            process::exit(1);
+        }
+    };
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+    let puzzle = match client.puzzle(day) {
+        Ok(puzzle) => puzzle,
+        Err(e) => {
+            eprintln!("failed to read day {day}: {e}");
+            process::exit(1);
+        }
     };
+
+    // NOTE: keep the local copy up to date, part two is only included once part one is solved.
+    let puzzle_path = year.data_dir().join("puzzles").join(format!("{day}.md"));
+    if let Err(e) = fs::write(puzzle_path, &puzzle) {
+        eprintln!("failed to write puzzle: {e}");
+    }
+
+    println!("{puzzle}");
 }
@@ -1,26 +1,57 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::{Day, Year};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
+fn safe_create_file(path: &Path) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+fn create_file(path: &Path) -> Result<File, std::io::Error> {
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Renders the module template for `day`. Solutions of years other than the primary year declare their year.
+fn render_module(year: Year, day: Day) -> String {
+    let day_number = day.into_inner().to_string();
+    let module = MODULE_TEMPLATE.replace("%DAY_NUMBER%", &day_number);
+
+    if year.is_primary() {
+        module
+    } else {
+        module.replace(
+            &format!("solution!({day_number})"),
+            &format!("solution!({day_number}, year = {year})"),
+        )
+    }
+}
+
+pub fn handle(year: Year, day: Day) {
+    let data_dir = year.data_dir();
+    let input_path = data_dir.join("inputs").join(format!("{day}.txt"));
+    let example_path = data_dir.join("examples").join(format!("{day}.txt"));
+    let example_answers_path = example_path.with_extension("answers");
+    let module_path = Path::new("src")
+        .join("bin")
+        .join(format!("{}.rs", year.bin_name(day)));
+
+    if let Err(e) = fs::create_dir_all(data_dir.join("inputs"))
+        .and_then(|()| fs::create_dir_all(data_dir.join("examples")))
+    {
+        eprintln!("Failed to create data directories: {e}");
+        process::exit(1);
+    }
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
@@ -30,13 +61,9 @@ pub fn handle(day: Day) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(render_module(year, day).as_bytes()) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!("Created module file \"{}\"", module_path.display());
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
@@ -46,7 +73,7 @@ pub fn handle(day: Day) {
 
     match create_file(&input_path) {
         Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+            println!("Created empty input file \"{}\"", input_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
@@ -56,7 +83,7 @@ pub fn handle(day: Day) {
 
     match create_file(&example_path) {
         Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+            println!("Created empty example file \"{}\"", example_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
@@ -64,6 +91,23 @@ pub fn handle(day: Day) {
         }
     }
 
+    match create_file(&example_answers_path) {
+        Ok(_) => {
+            println!(
+                "Created empty example answers file \"{}\"",
+                example_answers_path.display()
+            );
+        }
+        Err(e) => {
+            eprintln!("Failed to create example answers file: {e}");
+            process::exit(1);
+        }
+    }
+
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    if year.is_primary() {
+        println!("🎄 Type `cargo solve {day}` to run your solution.");
+    } else {
+        println!("🎄 Type `cargo solve {day} --year {year}` to run your solution.");
+    }
 }
@@ -1,9 +1,22 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{limits, params::Params, record::OutputFormat, Day, RunOptions, Year};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// Runs the solution of `day`. Of `options`, only `is_release`, `threads` and `limits` apply.
+/// `params` override the puzzle parameters of the solution, see [`params`](crate::template::params).
+/// `variant` runs the variants of that name instead of their parts, see [`variants`](crate::template::variants).
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Year,
+    day: Day,
+    dhat: bool,
+    submit_part: Option<u8>,
+    format: Option<OutputFormat>,
+    options: RunOptions,
+    params: &Params,
+    variant: Option<&str>,
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), year.bin_name(day)];
 
     if dhat {
         cmd_args.extend([
@@ -12,12 +25,30 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
             "--features".to_string(),
             "dhat-heap".to_string(),
         ]);
-    } else if release {
+    } else if options.is_release {
         cmd_args.push("--release".to_string());
     }
 
     cmd_args.push("--".to_string());
 
+    if format == Some(OutputFormat::Json) {
+        cmd_args.push("--format".to_string());
+        cmd_args.push("json".to_string());
+    }
+
+    if let Some(threads) = options.threads {
+        cmd_args.push("--threads".to_string());
+        cmd_args.push(threads.to_string());
+    }
+
+    cmd_args.extend(options.limits.to_args());
+    cmd_args.extend(params.to_args());
+
+    if let Some(variant) = variant {
+        cmd_args.push("--variant".to_string());
+        cmd_args.push(variant.to_string());
+    }
+
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
@@ -30,5 +61,10 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    let status = cmd.wait().unwrap();
+
+    // NOTE: pass on that a part was stopped by a limit, so scripts can tell it apart from a wrong answer.
+    if status.code() == Some(limits::EXIT_CODE) {
+        process::exit(limits::EXIT_CODE);
+    }
 }
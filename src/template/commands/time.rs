@@ -1,16 +1,85 @@
 use std::collections::HashSet;
+use std::process;
 
-use crate::template::run_multi::run_multi;
+use crate::template::cold::run_cold;
+use crate::template::compare::{compare, has_regressions, print_comparisons};
+use crate::template::complexity::{self, run_complexity};
+use crate::template::run_multi::{run_multi, RunOptions};
+use crate::template::scaling::run_scaling;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::variants::run_variants;
+use crate::template::{all_days, readme_benchmarks, Day, Year};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// How the solutions are benched.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// Many samples in one process with warm caches.
+    #[default]
+    Warm,
+    /// Warm benches at increasing thread counts, see [`run_scaling`].
+    Scaling,
+    /// One sample per fresh process with evicted caches, repeated `runs` times, see [`run_cold`].
+    Cold { runs: usize },
+    /// Warm benches of every variant next to its part, see [`run_variants`].
+    Variants,
+    /// Benches over a ladder of input sizes with a fitted complexity, see [`run_complexity`].
+    Complexity(complexity::Options),
+}
+
+/// Default relative change (in percent) below which a difference to the stored timings is treated as noise.
+pub const DEFAULT_COMPARE_THRESHOLD: f64 = 5.0;
+
+/// Runs benchmarks. If `compare_threshold` is set, the results are diffed against the stored timings
+/// and the process exits with a non-zero status if any part got slower by more than the threshold (in percent).
+/// It also exits with a non-zero status if any part failed.
+/// Scaling, cold, variant and complexity runs only print their results, see [`Mode`], so they can't be stored or
+/// compared.
+/// Variant runs exit with a non-zero status if any variant disagrees with its part.
+/// `options` control what is recorded besides durations, solutions are always benched in release mode.
+pub fn handle(
+    year: Year,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    compare_threshold: Option<f64>,
+    mode: Mode,
+    options: RunOptions,
+) {
+    let selected_days = || day.map_or_else(|| all_days().collect(), |day| vec![day]);
+
+    match mode {
+        Mode::Warm => {}
+        Mode::Scaling => {
+            run_scaling(year, &selected_days(), options.threads, options.is_counters);
+            return;
+        }
+        Mode::Cold { runs } => {
+            run_cold(year, &selected_days(), runs);
+            return;
+        }
+        Mode::Variants => {
+            if !run_variants(year, &selected_days(), &options) {
+                process::exit(1);
+            }
+            return;
+        }
+        Mode::Complexity(complexity_options) => {
+            if !run_complexity(year, &selected_days(), &complexity_options) {
+                process::exit(1);
+            }
+            return;
+        }
+    }
+
+    let stored_timings = Timings::read_from_file(year);
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
                 all_days().collect()
+            } else if compare_threshold.is_some() {
+                // when comparing, bench every day that has a baseline.
+                stored_timings.data.iter().map(|t| t.day).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
                 all_days()
@@ -21,20 +90,52 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let options = RunOptions {
+        is_release: true,
+        is_timed: true,
+        ..options
+    };
+    let run = run_multi(year, &days_to_run, &options);
+    if run.error.is_some() {
+        // NOTE: days the run didn't get to would be stored as unbenched or compared as lost.
+        eprintln!("Not comparing or storing the timings of an incomplete run.");
+        process::exit(1);
+    }
+    let is_ok = run.is_ok();
+    let timings = run.timings.unwrap_or_default();
+
+    let regressed = compare_threshold.is_some_and(|threshold| {
+        // NOTE: only days that were benched can be lost, a single day is not compared against every baseline.
+        let baseline = Timings {
+            data: stored_timings
+                .data
+                .iter()
+                .filter(|t| days_to_run.contains(&t.day))
+                .cloned()
+                .collect(),
+        };
+        let comparisons = compare(&baseline, &timings, threshold);
+        println!();
+        print_comparisons(&comparisons, threshold);
+        has_regressions(&comparisons)
+    });
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year).unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(year, merged_timings) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+            Err(e) => {
+                eprintln!("Failed to store updated benchmarks: {e}");
             }
         }
     }
+
+    if regressed || !is_ok {
+        process::exit(1);
+    }
 }
@@ -0,0 +1,112 @@
+use std::{collections::BTreeSet, process};
+
+use crate::template::answers::{AnswerStatus, Answers};
+use crate::template::limits::Limits;
+use crate::template::record::{part_label, PARSE_PART};
+use crate::template::run_multi::child_commands;
+use crate::template::{all_days, Day, RunOptions, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Runs optimized builds of the solutions of `year` on all of their inputs and checks every answer against the answers
+/// recorded in the `answers` folder of its data directory.
+/// Exits with a non-zero status if any answer differs from its recorded answer, any part failed, including parts
+/// stopped by `limits`, or a part with a recorded answer was never run.
+pub fn handle(year: Year, day: Option<Day>, limits: Limits) {
+    let days: Vec<Day> = day.map_or_else(|| all_days().collect(), |day| vec![day]);
+
+    let mut correct = 0;
+    let mut incorrect = 0;
+    let mut unknown = 0;
+
+    // every part with a recorded answer is expected to report one.
+    let mut expected = BTreeSet::new();
+    for &day in &days {
+        match Answers::read_all(year, day) {
+            Ok(all) => {
+                for (input, answers) in all {
+                    for part in [1, 2]
+                        .into_iter()
+                        .filter(|&part| answers.get(part).is_some())
+                    {
+                        expected.insert((day, input.clone(), part));
+                    }
+                }
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                incorrect += 1;
+            }
+        }
+    }
+
+    println!("{ANSI_BOLD}Verifying answers{ANSI_RESET}");
+    println!("------");
+
+    let options = RunOptions {
+        is_release: true,
+        limits,
+        ..RunOptions::default()
+    };
+
+    let output = child_commands::run_solutions(year, &days, &options, |record| {
+        expected.remove(&(record.day, record.input.clone(), record.part));
+
+        let input = record
+            .input
+            .as_ref()
+            .map_or_else(String::new, |name| format!(" ({name})"));
+
+        if let Some(failure) = &record.failure {
+            incorrect += 1;
+            println!(
+                "Day {} {}{input}: ✖ · {failure}",
+                record.day,
+                part_label(record.part)
+            );
+            if let Some(message) = failure.message() {
+                println!("  {ANSI_ITALIC}{message}{ANSI_RESET}");
+            }
+            return;
+        }
+
+        if record.part == PARSE_PART {
+            return;
+        }
+
+        let status = match Answers::read(year, record.day, record.input.as_deref()) {
+            Ok(answers) => answers.check(record.part, record.answer.as_deref()),
+            Err(e) => {
+                eprintln!("{e}");
+                AnswerStatus::Unknown
+            }
+        };
+
+        match status {
+            AnswerStatus::Correct => correct += 1,
+            AnswerStatus::Incorrect(_) => incorrect += 1,
+            AnswerStatus::Unknown => unknown += 1,
+        }
+
+        let answer = record.answer.as_deref().unwrap_or("✖");
+        println!(
+            "Day {} Part {}{input}: {answer} · {status}",
+            record.day, record.part
+        );
+    });
+
+    if let Err(e) = output {
+        eprintln!("Failed to run solutions: {e}");
+        process::exit(1);
+    }
+
+    for (day, input, part) in expected {
+        incorrect += 1;
+        let input = input.map_or_else(String::new, |name| format!(" ({name})"));
+        println!("Day {day} Part {part}{input}: ✖ · no answer reported");
+    }
+
+    println!("\n{correct} correct, {incorrect} incorrect, {unknown} unknown.");
+
+    if incorrect > 0 {
+        process::exit(1);
+    }
+}
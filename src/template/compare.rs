@@ -0,0 +1,289 @@
+/// Compares fresh benchmark results against stored timings.
+use std::{fmt::Display, time::Duration};
+
+use crate::template::{
+    record::{part_label, PARSE_PART},
+    timings::{Timing, Timings},
+    Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Faster,
+    Slower,
+    Unchanged,
+    /// The baseline has no timing for the part, e.g. because it is new.
+    Missing,
+    /// The baseline has a timing for the part, but the current run has none, e.g. because it failed.
+    Lost,
+}
+
+/// Comparison of a single part against its baseline.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartComparison {
+    pub day: Day,
+    pub part: u8,
+    pub baseline: Option<Duration>,
+    pub current: Option<Duration>,
+    pub verdict: Verdict,
+}
+
+impl PartComparison {
+    /// Relative change from baseline to current in percent.
+    pub fn change_percent(&self) -> Option<f64> {
+        #[allow(clippy::cast_precision_loss)]
+        let (baseline, current) = (
+            self.baseline?.as_nanos() as f64,
+            self.current?.as_nanos() as f64,
+        );
+        if baseline == 0.0 {
+            return None;
+        }
+        Some((current - baseline) * 100.0 / baseline)
+    }
+}
+
+/// Picks the value a part is compared by: the median if the distribution was stored, the mean otherwise.
+fn part_duration(timing: &Timing, part: u8) -> Option<Duration> {
+    let part = match part {
+        PARSE_PART => timing.parse.as_ref(),
+        1 => timing.part_1.as_ref(),
+        _ => timing.part_2.as_ref(),
+    }?;
+
+    Some(part.stats.map_or_else(|| part.duration(), |s| s.median))
+}
+
+/// Compares every part of `current` with the corresponding part of `baseline`.
+/// Days and parts of the baseline that are missing from `current` are reported as [`Verdict::Lost`].
+pub fn compare(baseline: &Timings, current: &Timings, threshold: f64) -> Vec<PartComparison> {
+    let mut comparisons = vec![];
+
+    let mut days: Vec<Day> = baseline
+        .data
+        .iter()
+        .chain(&current.data)
+        .map(|t| t.day)
+        .collect();
+    days.sort_unstable();
+    days.dedup();
+
+    for day in days {
+        let stored = baseline.data.iter().find(|t| t.day == day);
+        let timing = current.data.iter().find(|t| t.day == day);
+
+        for part in [PARSE_PART, 1, 2] {
+            let current = timing.and_then(|t| part_duration(t, part));
+            let baseline = stored.and_then(|t| part_duration(t, part));
+
+            if current.is_none() && baseline.is_none() {
+                continue;
+            }
+
+            let mut comparison = PartComparison {
+                day,
+                part,
+                baseline,
+                current,
+                verdict: Verdict::Missing,
+            };
+
+            comparison.verdict = match comparison.change_percent() {
+                None if current.is_none() => Verdict::Lost,
+                None => Verdict::Missing,
+                Some(x) if x > threshold => Verdict::Slower,
+                Some(x) if x < -threshold => Verdict::Faster,
+                Some(_) => Verdict::Unchanged,
+            };
+
+            comparisons.push(comparison);
+        }
+    }
+
+    comparisons
+}
+
+/// Returns `true` if any part got slower than the threshold allows, or lost its timing.
+pub fn has_regressions(comparisons: &[PartComparison]) -> bool {
+    comparisons
+        .iter()
+        .any(|c| matches!(c.verdict, Verdict::Slower | Verdict::Lost))
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Verdict::Faster => "faster",
+            Verdict::Slower => "slower",
+            Verdict::Unchanged => "unchanged",
+            Verdict::Missing => "no baseline",
+            Verdict::Lost => "lost",
+        })
+    }
+}
+
+impl Display for PartComparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let format = |d: Option<Duration>| d.map_or_else(|| "-".into(), |d| format!("{d:.1?}"));
+
+        write!(
+            f,
+            "Day {} {}: {} → {}",
+            self.day,
+            part_label(self.part),
+            format(self.baseline),
+            format(self.current)
+        )?;
+
+        if let Some(change) = self.change_percent() {
+            write!(f, " ({change:+.1}%)")?;
+        }
+
+        match self.verdict {
+            Verdict::Slower | Verdict::Lost => {
+                write!(f, " {ANSI_BOLD}{}{ANSI_RESET}", self.verdict)
+            }
+            _ => write!(f, " {ANSI_ITALIC}{}{ANSI_RESET}", self.verdict),
+        }
+    }
+}
+
+pub fn print_comparisons(comparisons: &[PartComparison], threshold: f64) {
+    println!("{ANSI_BOLD}Comparison with stored timings{ANSI_RESET} (threshold: ±{threshold:.1}%)");
+    println!("------");
+
+    if comparisons.is_empty() {
+        println!("No stored timings to compare against.");
+        return;
+    }
+
+    for comparison in comparisons {
+        println!("{comparison}");
+    }
+
+    let regressions = comparisons
+        .iter()
+        .filter(|c| c.verdict == Verdict::Slower)
+        .count();
+    let lost = comparisons
+        .iter()
+        .filter(|c| c.verdict == Verdict::Lost)
+        .count();
+    let improvements = comparisons
+        .iter()
+        .filter(|c| c.verdict == Verdict::Faster)
+        .count();
+
+    println!("\n{regressions} slower, {lost} lost, {improvements} faster.");
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{compare, has_regressions, Verdict};
+    use crate::{
+        day,
+        template::{
+            stats::Stats,
+            timings::{Timing, Timings},
+        },
+    };
+
+    fn timing(part_1: Option<u64>, part_2: Option<u64>) -> Timing {
+        let part = |x: Option<u64>| x.map(|x| Stats::single(Duration::from_nanos(x)).into());
+        Timing {
+            day: day!(1),
+            parse: None,
+            part_1: part(part_1),
+            part_2: part(part_2),
+            total_nanos: 0.0,
+            commit: None,
+            toolchain: None,
+        }
+    }
+
+    #[test]
+    fn detects_regressions() {
+        let baseline = Timings {
+            data: vec![timing(Some(100), Some(100))],
+        };
+        let current = Timings {
+            data: vec![timing(Some(104), Some(120))],
+        };
+        let res = compare(&baseline, &current, 5.0);
+        assert_eq!(res[0].verdict, Verdict::Unchanged);
+        assert_eq!(res[1].verdict, Verdict::Slower);
+        assert_eq!(res[1].change_percent(), Some(20.0));
+        assert!(has_regressions(&res));
+    }
+
+    #[test]
+    fn detects_improvements() {
+        let baseline = Timings {
+            data: vec![timing(Some(100), None)],
+        };
+        let current = Timings {
+            data: vec![timing(Some(50), Some(10))],
+        };
+        let res = compare(&baseline, &current, 5.0);
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].verdict, Verdict::Faster);
+        assert_eq!(res[1].verdict, Verdict::Missing);
+        assert!(!has_regressions(&res));
+    }
+
+    #[test]
+    fn reports_lost_parts_and_days() {
+        let mut other_day = timing(Some(100), Some(100));
+        other_day.day = day!(2);
+        let baseline = Timings {
+            data: vec![timing(Some(100), Some(100)), other_day],
+        };
+        let current = Timings {
+            data: vec![timing(Some(100), None)],
+        };
+        let res = compare(&baseline, &current, 5.0);
+        assert_eq!(res.len(), 4);
+        assert_eq!(res[0].verdict, Verdict::Unchanged);
+        assert_eq!(res[1].verdict, Verdict::Lost);
+        assert_eq!((res[2].day, res[2].verdict), (day!(2), Verdict::Lost));
+        assert_eq!((res[3].day, res[3].verdict), (day!(2), Verdict::Lost));
+        assert!(has_regressions(&res));
+    }
+
+    #[test]
+    fn falls_back_to_mean() {
+        let mut stored = timing(None, None);
+        stored.part_1 = Some(Duration::from_nanos(1500).into());
+        let baseline = Timings { data: vec![stored] };
+        let current = Timings {
+            data: vec![timing(Some(3000), None)],
+        };
+        let res = compare(&baseline, &current, 5.0);
+        assert_eq!(res[0].baseline, Some(Duration::from_nanos(1500)));
+        assert_eq!(res[0].verdict, Verdict::Slower);
+    }
+
+    #[test]
+    fn compares_parse_step() {
+        let with_parse = |nanos: u64| Timing {
+            parse: Some(Stats::single(Duration::from_nanos(nanos)).into()),
+            ..timing(Some(100), None)
+        };
+        let baseline = Timings {
+            data: vec![with_parse(100)],
+        };
+        let current = Timings {
+            data: vec![with_parse(200)],
+        };
+        let res = compare(&baseline, &current, 5.0);
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].part, 0);
+        assert_eq!(res[0].verdict, Verdict::Slower);
+        assert_eq!(res[1].verdict, Verdict::Unchanged);
+        assert_eq!(res[0].to_string().starts_with("Day 01 Parse: "), true);
+    }
+}
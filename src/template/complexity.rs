@@ -0,0 +1,538 @@
+/// Empirical complexity of solutions over a ladder of input sizes.
+///
+/// `cargo time --scale` solves inputs of growing size with every day and fits the durations to `c · n^k`, where `n`
+/// is the length of the input in bytes. The exponent and the closest of the common complexity classes tell whether
+/// an optimization changed the asymptotics of a solution or only its constant factor. Inputs come from the
+/// [`generator`](crate::generate::generator) of a day, days without one repeat the lines of their input instead.
+/// So do days whose generator caps its sizes so early that too few of them grow the input to fit a curve, or they
+/// are skipped if there is no input.
+use std::{
+    env,
+    fmt::Display,
+    hint::black_box,
+    process::{Command, Stdio},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    generate::{self, Generated, Generator},
+    template::{
+        alloc::format_bytes, inputs::read_inputs, panics, params, solution::Registration,
+        stats::Stats, Day, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+    },
+};
+
+/// Number of sizes of the ladder, each one half of the next.
+const STEPS: u32 = 7;
+/// Time spent re-solving each input of the ladder to get a stable median.
+const BUDGET: Duration = Duration::from_millis(200);
+const MAX_RUNS: usize = 1000;
+/// Inputs that take longer than this to solve end the ladder.
+const MAX_DURATION: Duration = Duration::from_secs(2);
+/// Generated inputs have to grow over at least this many sizes of the ladder, or the input is repeated instead.
+const MIN_GROWING_SIZES: usize = 3;
+
+const PLOT_WIDTH: usize = 48;
+const PLOT_HEIGHT: usize = 12;
+
+/// Which inputs the ladder is made of.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Options {
+    /// Size of the largest input, see [`Generator`]. Days without a generator repeat their input this often.
+    pub max_size: usize,
+    /// Seed every input is generated from, so ladders of different runs are comparable.
+    pub seed: u64,
+    /// Whether to plot the durations over the input lengths.
+    pub is_plot: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            max_size: 1024,
+            seed: 0,
+            is_plot: false,
+        }
+    }
+}
+
+impl Options {
+    /// Reads the `--size <max size>`, `--seed <seed>` and `--plot` flags from the process arguments.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        let value = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .and_then(|i| args.get(i + 1)?.parse().ok())
+        };
+
+        let default = Options::default();
+        Options {
+            max_size: value("--size").map_or(default.max_size, |x| x as usize),
+            seed: value("--seed").unwrap_or(default.seed),
+            is_plot: args.iter().any(|x| x == "--plot"),
+        }
+    }
+
+    /// Sizes of the ladder, doubling up to the largest size.
+    fn ladder(&self) -> Vec<usize> {
+        let mut sizes: Vec<usize> = (0..STEPS)
+            .rev()
+            .map(|i| self.max_size >> i)
+            .filter(|&size| size > 0)
+            .collect();
+        sizes.dedup();
+        sizes
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Common complexity classes that fits are compared with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Class {
+    Constant,
+    Logarithmic,
+    Linear,
+    Linearithmic,
+    Quadratic,
+    Cubic,
+}
+
+impl Class {
+    const ALL: [Class; 6] = [
+        Class::Constant,
+        Class::Logarithmic,
+        Class::Linear,
+        Class::Linearithmic,
+        Class::Quadratic,
+        Class::Cubic,
+    ];
+
+    fn eval(self, n: f64) -> f64 {
+        let log = n.ln().max(1.0);
+        match self {
+            Class::Constant => 1.0,
+            Class::Logarithmic => log,
+            Class::Linear => n,
+            Class::Linearithmic => n * log,
+            Class::Quadratic => n * n,
+            Class::Cubic => n * n * n,
+        }
+    }
+}
+
+impl Display for Class {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Class::Constant => "1",
+            Class::Logarithmic => "log n",
+            Class::Linear => "n",
+            Class::Linearithmic => "n log n",
+            Class::Quadratic => "n²",
+            Class::Cubic => "n³",
+        })
+    }
+}
+
+/// Durations fitted to `factor · n^exponent` by least squares on the log-log scale.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Fit {
+    pub exponent: f64,
+    /// Seconds at `n = 1`.
+    pub factor: f64,
+    /// Coefficient of determination on the log-log scale, `1.0` if every point is on the curve.
+    pub r_squared: f64,
+    /// The class whose curve is closest to the durations, up to a constant factor.
+    pub class: Class,
+}
+
+impl Fit {
+    /// Seconds the fitted curve predicts for an input of length `n`.
+    pub fn predict(&self, n: f64) -> f64 {
+        self.factor * n.powf(self.exponent)
+    }
+}
+
+impl Display for Fit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "n^{:.2} (r² {:.3}), closest to O({})",
+            self.exponent, self.r_squared, self.class
+        )
+    }
+}
+
+/// Fits durations in seconds measured at input lengths `n`, given as `(n, seconds)`.
+/// Returns `None` unless there are at least two distinct lengths.
+pub fn fit(points: &[(f64, f64)]) -> Option<Fit> {
+    let logs: Vec<(f64, f64)> = points
+        .iter()
+        .map(|&(n, seconds)| (n.ln(), seconds.max(f64::MIN_POSITIVE).ln()))
+        .collect();
+    #[allow(clippy::cast_precision_loss)]
+    let count = logs.len() as f64;
+    let mean_x = logs.iter().map(|(x, _)| x).sum::<f64>() / count;
+    let mean_y = logs.iter().map(|(_, y)| y).sum::<f64>() / count;
+
+    let sxx: f64 = logs.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    if sxx < f64::EPSILON {
+        return None;
+    }
+    let sxy: f64 = logs.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let exponent = sxy / sxx;
+    let intercept = mean_y - exponent * mean_x;
+
+    let ss_total: f64 = logs.iter().map(|(_, y)| (y - mean_y).powi(2)).sum();
+    let ss_residual: f64 = logs
+        .iter()
+        .map(|(x, y)| (y - intercept - exponent * x).powi(2))
+        .sum();
+    let r_squared = if ss_total > 0.0 {
+        1.0 - ss_residual / ss_total
+    } else {
+        1.0
+    };
+
+    // NOTE: the best constant factor of a class is the mean offset on the log scale, what is left is the spread.
+    let spread = |class: Class| {
+        let offsets: Vec<f64> = points
+            .iter()
+            .zip(&logs)
+            .map(|(&(n, _), (_, y))| y - class.eval(n).ln())
+            .collect();
+        let mean = offsets.iter().sum::<f64>() / count;
+        offsets.iter().map(|x| (x - mean).powi(2)).sum::<f64>()
+    };
+    let class = Class::ALL
+        .into_iter()
+        .min_by(|a, b| spread(*a).total_cmp(&spread(*b)))?;
+
+    Some(Fit {
+        exponent,
+        factor: intercept.exp(),
+        r_squared,
+        class,
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Where the inputs of a day come from.
+enum Source {
+    Generated(Generator),
+    /// The lines of the default input, repeated.
+    Repeated(String),
+}
+
+impl Source {
+    fn of(solution: &Registration) -> Option<Self> {
+        generate::generator(solution.year, solution.day)
+            .map(Source::Generated)
+            .or_else(|| Source::repeated(solution))
+    }
+
+    fn repeated(solution: &Registration) -> Option<Self> {
+        read_inputs((solution.year, solution.day))
+            .into_iter()
+            .find(|input| input.name.is_none())
+            .map(|input| Source::Repeated(input.data))
+    }
+
+    /// The inputs of every size of the ladder.
+    fn ladder(&self, options: &Options) -> Vec<(usize, Generated)> {
+        options
+            .ladder()
+            .into_iter()
+            .map(|size| (size, self.input(size, options.seed)))
+            .collect()
+    }
+
+    fn input(&self, size: usize, seed: u64) -> Generated {
+        match self {
+            Source::Generated(generator) => generate::generate(*generator, size, seed),
+            Source::Repeated(input) => repeat(input, size).into(),
+        }
+    }
+}
+
+/// Number of inputs that are longer than every smaller one.
+fn growing_sizes(inputs: &[(usize, Generated)]) -> usize {
+    let mut longest = 0;
+    inputs
+        .iter()
+        .filter(|(_, generated)| {
+            let is_growing = generated.input.len() > longest;
+            longest = longest.max(generated.input.len());
+            is_growing
+        })
+        .count()
+}
+
+/// `input` with its lines repeated `times` times.
+fn repeat(input: &str, times: usize) -> String {
+    let lines = input.trim_end_matches('\n');
+    vec![lines; times.max(1)].join("\n") + "\n"
+}
+
+/// Solves `generated` repeatedly for a while and returns the median duration, or how solving it failed.
+fn measure(solution: &Registration, generated: &Generated) -> Result<Duration, String> {
+    params::with(&generated.params, || {
+        let mut samples = vec![];
+        let timer = Instant::now();
+        while samples.is_empty() || (samples.len() < MAX_RUNS && timer.elapsed() < BUDGET) {
+            let start = Instant::now();
+            let answers =
+                panics::catch(|| black_box((solution.solve)(black_box(&generated.input))))?;
+            samples.push(start.elapsed());
+            if let Some(Err(e)) = answers.into_iter().find(Result::is_err) {
+                return Err(e);
+            }
+        }
+        Ok(Stats::from_samples(&mut samples).median)
+    })
+}
+
+/// Solves every selected solution over the ladder of sizes and prints the durations and their fit.
+pub fn run(solutions: &[&Registration], options: &Options) {
+    println!(
+        "{ANSI_BOLD}Scaling over input sizes{ANSI_RESET} {ANSI_ITALIC}up to size {}, seed {}{ANSI_RESET}",
+        options.max_size, options.seed
+    );
+
+    for solution in solutions {
+        println!();
+        println!("{ANSI_BOLD}Day {}{ANSI_RESET}", solution.day);
+        println!("------");
+
+        let Some(source) = Source::of(solution) else {
+            println!("No generator or input, skipped.");
+            continue;
+        };
+        let mut inputs = source.ladder(options);
+        match source {
+            Source::Repeated(_) => {
+                println!("{ANSI_ITALIC}No generator, repeating the input.{ANSI_RESET}");
+            }
+            Source::Generated(_) if growing_sizes(&inputs) < MIN_GROWING_SIZES => {
+                let Some(repeated) = Source::repeated(solution) else {
+                    println!("Generated inputs stop growing early and there is no input to repeat, skipped.");
+                    continue;
+                };
+                println!(
+                    "{ANSI_ITALIC}Generated inputs stop growing early, repeating the input.{ANSI_RESET}"
+                );
+                inputs = repeated.ladder(options);
+            }
+            Source::Generated(_) => {}
+        }
+
+        println!("Size     Input        Time");
+        let mut points: Vec<(f64, f64)> = vec![];
+        for (size, generated) in inputs {
+            #[allow(clippy::cast_precision_loss)]
+            let n = generated.input.len() as f64;
+            // NOTE: generators cap some sizes, e.g. to keep answers in range. Those inputs stop growing.
+            if points.last().is_some_and(|&(last, _)| n <= last) {
+                println!("{size:<8} {ANSI_ITALIC}Input stopped growing, skipped.{ANSI_RESET}");
+                continue;
+            }
+
+            let duration = match measure(solution, &generated) {
+                Ok(duration) => duration,
+                Err(e) => {
+                    println!("{size:<8} Failed: {e}");
+                    break;
+                }
+            };
+            println!(
+                "{size:<8} {:<12} {:.1?}",
+                format_bytes(generated.input.len() as u64),
+                duration
+            );
+            points.push((n, duration.as_secs_f64()));
+
+            if duration > MAX_DURATION {
+                println!("{ANSI_ITALIC}Too slow for larger sizes.{ANSI_RESET}");
+                break;
+            }
+        }
+
+        match fit(&points) {
+            Some(fit) => {
+                println!("Complexity: {fit}");
+                if options.is_plot {
+                    println!();
+                    for line in plot(&points, &fit) {
+                        println!("{line}");
+                    }
+                }
+            }
+            None => println!("Not enough sizes to fit a curve."),
+        }
+    }
+}
+
+/// Plots the points (`●`) and the fitted curve (`·`) on log-log axes.
+fn plot(points: &[(f64, f64)], fit: &Fit) -> Vec<String> {
+    let (min_x, max_x) = bounds(points.iter().map(|(n, _)| n.ln()));
+    let (min_y, max_y) = bounds(points.iter().map(|(_, t)| t.ln()));
+    let (min_y, max_y) = if (max_y - min_y).abs() < f64::EPSILON {
+        (min_y - 1.0, max_y + 1.0)
+    } else {
+        (min_y, max_y)
+    };
+
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    let cell = |x: f64, y: f64| {
+        let col = ((x - min_x) / (max_x - min_x) * (PLOT_WIDTH - 1) as f64).round();
+        let row = ((max_y - y) / (max_y - min_y) * (PLOT_HEIGHT - 1) as f64).round();
+        (0.0..PLOT_HEIGHT as f64)
+            .contains(&row)
+            .then_some((row as usize, col as usize))
+    };
+
+    let mut grid = vec![vec![' '; PLOT_WIDTH]; PLOT_HEIGHT];
+    for col in 0..PLOT_WIDTH {
+        #[allow(clippy::cast_precision_loss)]
+        let x = min_x + (max_x - min_x) * col as f64 / (PLOT_WIDTH - 1) as f64;
+        if let Some((row, col)) = cell(x, fit.predict(x.exp()).ln()) {
+            grid[row][col] = '·';
+        }
+    }
+    for &(n, t) in points {
+        if let Some((row, col)) = cell(n.ln(), t.ln()) {
+            grid[row][col] = '●';
+        }
+    }
+
+    let time = |y: f64| format!("{:.1?}", Duration::from_secs_f64(y.exp()));
+    let mut lines: Vec<String> = grid
+        .into_iter()
+        .enumerate()
+        .map(|(row, cells)| {
+            let label = match row {
+                0 => time(max_y),
+                _ if row == PLOT_HEIGHT - 1 => time(min_y),
+                _ => String::new(),
+            };
+            format!("{label:>10} │{}", cells.into_iter().collect::<String>())
+        })
+        .collect();
+    lines.push(format!("{:>10} └{}", "", "─".repeat(PLOT_WIDTH)));
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (first, last) = (
+        format_bytes(min_x.exp().round() as u64),
+        format_bytes(max_x.exp().round() as u64),
+    );
+    lines.push(format!(
+        "{:>10}  {first}{last:>width$}",
+        "",
+        width = PLOT_WIDTH.saturating_sub(first.chars().count())
+    ));
+    lines
+}
+
+fn bounds(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), x| {
+        (min.min(x), max.max(x))
+    })
+}
+
+/// Runs [`run`] for `days` of `year` in an optimized `aggregate` process.
+/// Returns whether the process succeeded.
+pub fn run_complexity(year: Year, days: &[Day], options: &Options) -> bool {
+    let mut args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--release".to_string(),
+        "--bin".to_string(),
+        "aggregate".to_string(),
+        "--".to_string(),
+        "--complexity".to_string(),
+        "--year".to_string(),
+        year.to_string(),
+        "--size".to_string(),
+        options.max_size.to_string(),
+        "--seed".to_string(),
+        options.seed.to_string(),
+    ];
+    if options.is_plot {
+        args.push("--plot".to_string());
+    }
+    args.extend(days.iter().map(Day::to_string));
+
+    match Command::new("cargo")
+        .args(&args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+    {
+        Ok(status) => status.success(),
+        Err(e) => {
+            eprintln!("Failed to run solutions: {e}");
+            false
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{fit, growing_sizes, repeat, Class, Options};
+
+    #[test]
+    fn doubles_sizes_up_to_max() {
+        let options = Options {
+            max_size: 256,
+            ..Options::default()
+        };
+        assert_eq!(options.ladder(), [4, 8, 16, 32, 64, 128, 256]);
+
+        let small = Options {
+            max_size: 3,
+            ..options
+        };
+        assert_eq!(small.ladder(), [1, 3]);
+    }
+
+    #[test]
+    fn fits_exponent_and_class() {
+        let linear: Vec<(f64, f64)> = [1e3, 1e4, 1e5, 1e6].map(|n| (n, n * 2e-9)).to_vec();
+        let fit_linear = fit(&linear).unwrap();
+        assert!((fit_linear.exponent - 1.0).abs() < 1e-9);
+        assert!((fit_linear.r_squared - 1.0).abs() < 1e-9);
+        assert_eq!(fit_linear.class, Class::Linear);
+
+        let linearithmic: Vec<(f64, f64)> = [1e3, 1e4, 1e5, 1e6]
+            .map(|n: f64| (n, n * n.ln() * 1e-9))
+            .to_vec();
+        assert_eq!(fit(&linearithmic).unwrap().class, Class::Linearithmic);
+
+        let quadratic: Vec<(f64, f64)> = [10.0, 20.0, 40.0].map(|n| (n, n * n)).to_vec();
+        assert_eq!(fit(&quadratic).unwrap().class, Class::Quadratic);
+
+        assert!(fit(&[(10.0, 1.0), (10.0, 2.0)]).is_none());
+    }
+
+    #[test]
+    fn counts_growing_sizes() {
+        let inputs =
+            ["ab", "abcd", "abcd", "abc", "abcdef"].map(|input| (0, input.to_string().into()));
+        assert_eq!(growing_sizes(&inputs), 3);
+        assert_eq!(growing_sizes(&[]), 0);
+    }
+
+    #[test]
+    fn repeats_lines() {
+        assert_eq!(repeat("a\nb\n", 3), "a\nb\na\nb\na\nb\n");
+        assert_eq!(repeat("a", 0), "a\n");
+    }
+}
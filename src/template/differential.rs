@@ -0,0 +1,389 @@
+/// Differential testing of solutions against their variants on generated inputs.
+///
+/// Variants are often slower but simpler implementations of a part, see [`variants`](super::variants). For every
+/// day with a [`generator`](crate::generate::generator), `cargo differential` solves random inputs with each part
+/// and its variants, which serve as the reference. When the part disagrees with the reference on a case, smaller sizes
+/// are searched for another counterexample, which is saved as an example with the answer of the reference in its
+/// sidecar, so `cargo test` keeps checking it, see [`examples`](super::examples). Generated inputs can't be shrunk
+/// themselves, so the smaller counterexample is a different input and may show a different bug. Variants that return `None`
+/// skip a case, e.g. because it is too large for them, but a variant that skips every case fails, as does a variant
+/// that panics or returns an error.
+use std::{env, fmt::Display, fs, path::PathBuf};
+
+use crate::{
+    generate::{self, Generated, Generator},
+    template::{
+        panics, params, solution::Registration, variants::Variant, Day, Year, ANSI_BOLD,
+        ANSI_ITALIC, ANSI_RESET,
+    },
+};
+
+/// Other seeds that are tried for each size while searching for a smaller counterexample.
+const SEARCH_SEEDS: u64 = 8;
+
+/// How many inputs are generated, and how.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Options {
+    pub cases: usize,
+    /// Seed of the first case, every later case uses the next one.
+    pub seed: u64,
+    /// Size of the last case. Sizes grow from `1` over the cases.
+    pub max_size: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            cases: 50,
+            seed: rand::random(),
+            max_size: 20,
+        }
+    }
+}
+
+impl Options {
+    /// Reads the `--cases <count>`, `--seed <seed>` and `--size <max size>` flags from the process arguments.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        let value = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .and_then(|i| args.get(i + 1)?.parse().ok())
+        };
+
+        let default = Options::default();
+        Options {
+            cases: value("--cases").map_or(default.cases, |x| x as usize),
+            seed: value("--seed").unwrap_or(default.seed),
+            max_size: value("--size").map_or(default.max_size, |x| x as usize),
+        }
+    }
+
+    /// Size and seed of every case.
+    fn cases(&self) -> impl Iterator<Item = (usize, u64)> + '_ {
+        let steps = self.cases.saturating_sub(1).max(1);
+        (0..self.cases).map(move |i| {
+            let size = 1 + i * self.max_size.saturating_sub(1) / steps;
+            (size, self.seed.wrapping_add(i as u64))
+        })
+    }
+}
+
+/// A generated input on which a part disagrees with one of its variants, or the variant itself fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    pub variant: Variant,
+    pub size: usize,
+    pub seed: u64,
+    /// Answer of the variant, or how it failed.
+    pub expected: Result<String, String>,
+    /// Answer of the part, or how it failed.
+    pub actual: Result<Option<String>, String>,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Variant { part, name } = self.variant;
+        let expected = match &self.expected {
+            Ok(expected) => expected,
+            Err(e) => {
+                return write!(
+                    f,
+                    "`{name}` of part {part} failed on size {}, seed {}: {e}",
+                    self.size, self.seed
+                );
+            }
+        };
+        let actual = match &self.actual {
+            Ok(answer) => format!("got {}", answer.as_deref().unwrap_or("nothing")),
+            Err(e) => e.clone(),
+        };
+        write!(
+            f,
+            "Part {part} disagrees with `{name}` on size {}, seed {}: expected {expected}, {actual}",
+            self.size, self.seed
+        )
+    }
+}
+
+/// How a part and one of its variants did on a single case.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Agrees,
+    /// The variant returned `None`, so there is nothing to compare against.
+    Skipped,
+    Disagrees(Mismatch),
+}
+
+/// How a part did against one of its variants over all cases.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Report {
+    pub variant: Variant,
+    /// Number of cases on which the part was compared with the variant.
+    pub compared: usize,
+    /// The smallest counterexample found for the first mismatch, see [`find_smaller`].
+    pub mismatch: Option<Mismatch>,
+}
+
+impl Report {
+    /// Whether the part agreed with the variant on at least one case and disagreed on none.
+    pub fn is_ok(&self) -> bool {
+        self.mismatch.is_none() && self.compared > 0
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Variant { part, name } = self.variant;
+        match &self.mismatch {
+            Some(mismatch) => write!(f, "{mismatch}"),
+            None if self.compared == 0 => {
+                write!(
+                    f,
+                    "`{name}` skipped every case, part {part} was never compared"
+                )
+            }
+            None => write!(
+                f,
+                "Part {part} agrees with `{name}` on {} cases",
+                self.compared
+            ),
+        }
+    }
+}
+
+/// Solves the case of `size` and `seed` with the part of `variant` and with `variant`.
+/// A variant that panics or returns an error disagrees, since the part can't be checked against it.
+pub fn check_case(
+    solution: &Registration,
+    generator: Generator,
+    variant: Variant,
+    size: usize,
+    seed: u64,
+) -> Outcome {
+    let generated = generate::generate(generator, size, seed);
+    let solve = |func: &dyn Fn() -> Result<Option<String>, String>| match panics::catch(|| {
+        params::with(&generated.params, func)
+    }) {
+        Ok(answer) => answer,
+        Err(message) => Err(format!("panicked: {message}")),
+    };
+
+    let (expected, actual) = match solve(&|| (solution.solve_variant)(&generated.input, variant)) {
+        Ok(None) => return Outcome::Skipped,
+        Ok(Some(expected)) => {
+            let actual = solve(&|| (solution.solve_part)(&generated.input, variant.part));
+            if actual.as_ref() == Ok(&Some(expected.clone())) {
+                return Outcome::Agrees;
+            }
+            (Ok(expected), actual)
+        }
+        Err(e) => (Err(e), Ok(None)),
+    };
+
+    Outcome::Disagrees(Mismatch {
+        variant,
+        size,
+        seed,
+        expected,
+        actual,
+    })
+}
+
+/// Checks every variant of `solution` on the cases of `options`, up to the first mismatch of each variant.
+pub fn check(solution: &Registration, generator: Generator, options: &Options) -> Vec<Report> {
+    solution
+        .metadata
+        .variants
+        .iter()
+        .map(|&variant| {
+            let mut compared = 0;
+            let mismatch = options.cases().find_map(|(size, seed)| {
+                match check_case(solution, generator, variant, size, seed) {
+                    Outcome::Agrees => compared += 1,
+                    Outcome::Skipped => {}
+                    Outcome::Disagrees(mismatch) => return Some(mismatch),
+                }
+                None
+            });
+            Report {
+                variant,
+                compared,
+                mismatch: mismatch.map(|mismatch| find_smaller(solution, generator, mismatch)),
+            }
+        })
+        .collect()
+}
+
+/// Searches smaller sizes for another case that disagrees, trying the seed of `mismatch` first, and returns the
+/// smallest one, or `mismatch` if there is none. The case is generated anew and not derived from the input of
+/// `mismatch`, so it may disagree for a different reason.
+fn find_smaller(solution: &Registration, generator: Generator, mismatch: Mismatch) -> Mismatch {
+    for size in 1..mismatch.size {
+        let seeds = (0..=SEARCH_SEEDS).map(|i| mismatch.seed.wrapping_add(i));
+        for seed in seeds {
+            if let Outcome::Disagrees(smaller) =
+                check_case(solution, generator, mismatch.variant, size, seed)
+            {
+                return smaller;
+            }
+        }
+    }
+
+    mismatch
+}
+
+/// Saves the input of `mismatch` as an example of `day`, with the answer of the variant and the parameters of the
+/// input in its sidecar. Returns the path of the example. Cases on which the variant failed have no answer to save.
+pub fn save(
+    year: Year,
+    day: Day,
+    generator: Generator,
+    mismatch: &Mismatch,
+) -> Result<PathBuf, String> {
+    let expected = mismatch
+        .expected
+        .as_ref()
+        .map_err(|_| format!("`{}` failed, so there is no answer.", mismatch.variant.name))?;
+    let Generated { input, params, .. } =
+        generate::generate(generator, mismatch.size, mismatch.seed);
+
+    let mut sidecar: Vec<String> = params
+        .iter()
+        .map(|(name, value)| format!("{name} = {value}"))
+        .collect();
+    sidecar.push(format!("{}: {expected}", mismatch.variant.part));
+
+    let dir = year.data_dir().join("examples");
+    let name = format!("{day}-diff-{}-{}", mismatch.size, mismatch.seed);
+    let path = dir.join(format!("{name}.txt"));
+
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    fs::write(&path, input).map_err(|e| e.to_string())?;
+    fs::write(
+        dir.join(format!("{name}.answers")),
+        sidecar.join("\n") + "\n",
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(path)
+}
+
+/// Checks every solution that has variants and a generator, saving the mismatches as examples.
+/// Returns whether every part was compared with its variants and agrees with them.
+pub fn run(solutions: &[&Registration], options: &Options) -> bool {
+    println!(
+        "{ANSI_BOLD}Differential testing{ANSI_RESET} {ANSI_ITALIC}{} cases up to size {}, seed {}{ANSI_RESET}",
+        options.cases, options.max_size, options.seed
+    );
+    println!("------");
+
+    let mut all_agree = true;
+    let mut is_empty = true;
+
+    for solution in solutions {
+        if solution.metadata.variants.is_empty() {
+            continue;
+        }
+        is_empty = false;
+
+        let Some(generator) = generate::generator(solution.year, solution.day) else {
+            println!("Day {}: no generator, skipped.", solution.day);
+            continue;
+        };
+
+        let reports = check(solution, generator, options);
+        if reports.iter().all(Report::is_ok) {
+            let compared: usize = reports.iter().map(|report| report.compared).sum();
+            println!(
+                "Day {}: all variants agree on {compared} cases.",
+                solution.day
+            );
+            continue;
+        }
+
+        all_agree = false;
+        for report in reports.iter().filter(|report| !report.is_ok()) {
+            println!("Day {}: ✖ {report}", solution.day);
+            let Some(mismatch) = &report.mismatch else {
+                continue;
+            };
+            match save(solution.year, solution.day, generator, mismatch) {
+                Ok(path) => println!("  {ANSI_ITALIC}Saved as {}{ANSI_RESET}", path.display()),
+                Err(e) => eprintln!("  Failed to save the counterexample: {e}"),
+            }
+        }
+    }
+
+    if is_empty {
+        println!("No variants to test against.");
+    }
+
+    all_agree
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Mismatch, Options, Report};
+    use crate::template::variants::Variant;
+
+    #[test]
+    fn ramps_sizes_over_cases() {
+        let options = Options {
+            cases: 5,
+            seed: u64::MAX - 1,
+            max_size: 9,
+        };
+        let cases: Vec<(usize, u64)> = options.cases().collect();
+        assert_eq!(
+            cases,
+            [(1, u64::MAX - 1), (3, u64::MAX), (5, 0), (7, 1), (9, 2)]
+        );
+
+        let single = Options {
+            cases: 1,
+            ..options
+        };
+        assert_eq!(single.cases().collect::<Vec<_>>(), [(1, u64::MAX - 1)]);
+    }
+
+    #[test]
+    fn fails_variants_that_never_compare() {
+        let variant = Variant {
+            name: "brute_force",
+            part: 2,
+        };
+        let report = Report {
+            variant,
+            compared: 0,
+            mismatch: None,
+        };
+        assert!(!report.is_ok());
+        assert!(report.to_string().contains("never compared"));
+
+        let failed = Report {
+            compared: 3,
+            mismatch: Some(Mismatch {
+                variant,
+                size: 2,
+                seed: 7,
+                expected: Err("panicked: oops".into()),
+                actual: Ok(None),
+            }),
+            ..report
+        };
+        assert!(!failed.is_ok());
+        assert_eq!(
+            failed.to_string(),
+            "`brute_force` of part 2 failed on size 2, seed 7: panicked: oops"
+        );
+        assert!(Report {
+            compared: 1,
+            mismatch: None,
+            ..report
+        }
+        .is_ok());
+    }
+}
@@ -0,0 +1,198 @@
+/// Checks solutions against the examples of their puzzles.
+///
+/// An example `examples/NN.txt` or `examples/NN-<suffix>.txt` is checked if it has a sidecar file next to it, e.g.
+/// `examples/10-2.answers`, holding its expected answers in the same format as [`Answers`]:
+///
+/// ```text
+/// 2: 4
+/// ```
+///
+/// Only the parts listed in the sidecar are run, so examples that only apply to one part are fine. Variants of a part
+/// are checked against the answer of the part, see [`variants`](super::variants).
+/// Puzzles that use other constants for their examples set them as [`params`] in the sidecar, one per line:
+///
+/// ```text
+/// steps = 6
+/// 1: 16
+/// ```
+///
+/// The tests of the `aggregate` binary run [`check_examples`] for every solution, so `cargo test` checks every example.
+use std::{fs, path::Path};
+
+use crate::template::{
+    answers::Answers,
+    panics,
+    params::{self, Params},
+    runner::step_label,
+    solution::Registration,
+    Day,
+};
+
+/// An example input together with its expected answers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    /// File stem of the example, e.g. `10-2`.
+    pub name: String,
+    pub data: String,
+    pub answers: Answers,
+    /// Parameters the example is solved with.
+    pub params: Params,
+}
+
+/// Parses a sidecar into the expected answers and the parameters of its example.
+fn parse_sidecar(s: &str) -> Result<(Answers, Params), String> {
+    let mut answers = vec![];
+    let mut pairs = vec![];
+
+    for line in s.lines() {
+        match params::parse_pair(line) {
+            Ok(pair) => pairs.push(pair),
+            Err(_) => answers.push(line),
+        }
+    }
+
+    Ok((answers.join("\n").parse()?, pairs.into_iter().collect()))
+}
+
+/// Reads every example of a day that has a sidecar with expected answers, ordered by name.
+fn read_examples_from(dir: &Path, day: Day) -> Result<Vec<Example>, String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(vec![]);
+    };
+
+    let prefix = day.to_string();
+    let mut examples = vec![];
+
+    for entry in entries {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        let Some(name) = path.file_stem().and_then(|x| x.to_str()) else {
+            continue;
+        };
+
+        let is_example = path.extension().is_some_and(|x| x == "txt")
+            && (name == prefix || name.starts_with(&format!("{prefix}-")));
+        let sidecar = path.with_extension("answers");
+
+        if !is_example || !sidecar.exists() {
+            continue;
+        }
+
+        let (answers, params) = fs::read_to_string(&sidecar)
+            .map_err(|e| e.to_string())
+            .and_then(|s| parse_sidecar(&s))
+            .map_err(|e| format!("Invalid sidecar {}: {e}", sidecar.display()))?;
+
+        examples.push(Example {
+            name: name.to_string(),
+            data: fs::read_to_string(&path).map_err(|e| e.to_string())?,
+            answers,
+            params,
+        });
+    }
+
+    examples.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+    Ok(examples)
+}
+
+/// Runs a solution and its variants on each of its examples and returns a description of every answer that differs
+/// from the expected answer. Parts that panic or return an error are reported as failures, too.
+pub fn check_examples(solution: &Registration) -> Vec<String> {
+    let dir = solution.year.data_dir().join("examples");
+    let examples = match read_examples_from(&dir, solution.day) {
+        Ok(examples) => examples,
+        Err(e) => return vec![format!("Day {}: {e}", solution.day)],
+    };
+
+    let mut failures = vec![];
+
+    for example in &examples {
+        // NOTE: variants are checked against the same answers as their parts.
+        let steps = solution
+            .metadata
+            .parts
+            .iter()
+            .map(|&part| (part, None))
+            .chain(
+                solution
+                    .metadata
+                    .variants
+                    .iter()
+                    .map(|v| (v.part, Some(*v))),
+            );
+
+        for (part, variant) in steps {
+            let Some(expected) = example.answers.get(part) else {
+                continue;
+            };
+
+            let label = format!(
+                "{} Day {} {} (example {})",
+                solution.year,
+                solution.day,
+                step_label(part, variant.map(|v| v.name)),
+                example.name
+            );
+
+            let solve = || match variant {
+                Some(variant) => (solution.solve_variant)(&example.data, variant),
+                None => (solution.solve_part)(&example.data, part),
+            };
+
+            match panics::catch(|| params::with(&example.params, solve)) {
+                Ok(Ok(Some(answer))) if answer == expected => {}
+                // NOTE: variants return nothing for inputs they skip.
+                Ok(Ok(None)) if variant.is_some() => {}
+                Ok(Ok(answer)) => failures.push(format!(
+                    "{label}: expected {expected}, got {}",
+                    answer.as_deref().unwrap_or("nothing")
+                )),
+                Ok(Err(error)) => failures.push(format!("{label}: error: {error}")),
+                Err(message) => failures.push(format!("{label}: panicked: {message}")),
+            }
+        }
+    }
+
+    failures
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::{parse_sidecar, read_examples_from};
+    use crate::{day, template::params::Params};
+
+    #[test]
+    fn reads_examples_with_answers() {
+        let dir = env::temp_dir().join(format!("aoc-examples-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for (name, answers) in [("10", "1: 4"), ("10-2", "2: 8"), ("01", "1: 1")] {
+            fs::write(dir.join(format!("{name}.txt")), name).unwrap();
+            fs::write(dir.join(format!("{name}.answers")), answers).unwrap();
+        }
+        fs::write(dir.join("10-3.txt"), "no answers").unwrap();
+
+        let examples = read_examples_from(&dir, day!(10)).unwrap();
+        let names: Vec<&str> = examples.iter().map(|x| x.name.as_str()).collect();
+        assert_eq!(names, ["10", "10-2"]);
+        assert_eq!(examples[1].data, "10-2");
+        assert_eq!(examples[1].answers.get(1), None);
+        assert_eq!(examples[1].answers.get(2), Some("8"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn reads_params_from_sidecars() {
+        let (answers, params) =
+            parse_sidecar("test_area_min = 7\ntest_area_max=27\n1: 2\n").unwrap();
+        assert_eq!(answers.get(1), Some("2"));
+        assert_eq!(
+            params,
+            Params::from([("test_area_min", "7"), ("test_area_max", "27")])
+        );
+        assert!(parse_sidecar("steps 6").is_err());
+    }
+}
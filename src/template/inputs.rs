@@ -0,0 +1,85 @@
+/// Puzzle inputs of a day.
+///
+/// Every day has a default input in `inputs/NN.txt`. Additional inputs, e.g. the inputs of teammates, can be added
+/// as `inputs/NN/<name>.txt` to check that a solution works for any user's input.
+/// Expected answers of named inputs live in `answers/NN/<name>.txt`, see [`Answers`](super::answers::Answers).
+use std::{fs, path::Path};
+
+use crate::template::{Day, Year};
+
+/// A single input of a day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Input {
+    /// Name of the input, `None` for the default input.
+    pub name: Option<String>,
+    pub data: String,
+}
+
+impl Input {
+    /// Human readable name of the input, `default` for the default input.
+    pub fn label(&self) -> &str {
+        self.name.as_deref().unwrap_or("default")
+    }
+}
+
+/// Reads every input of a day: the default input first, followed by named inputs ordered by name.
+/// Inputs that don't exist are skipped, so the result may be empty.
+pub fn read_inputs(day: impl Into<(Year, Day)>) -> Vec<Input> {
+    let (year, day) = day.into();
+    read_inputs_from(&year.data_dir().join("inputs"), day)
+}
+
+fn read_inputs_from(dir: &Path, day: Day) -> Vec<Input> {
+    let mut inputs: Vec<Input> = fs::read_to_string(dir.join(format!("{day}.txt")))
+        .ok()
+        .map(|data| Input { name: None, data })
+        .into_iter()
+        .collect();
+
+    let mut named: Vec<Input> = fs::read_dir(dir.join(day.to_string()))
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "txt" {
+                return None;
+            }
+            Some(Input {
+                name: Some(path.file_stem()?.to_str()?.to_string()),
+                data: fs::read_to_string(&path).ok()?,
+            })
+        })
+        .collect();
+    named.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+
+    inputs.extend(named);
+    inputs
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::read_inputs_from;
+    use crate::day;
+
+    #[test]
+    fn reads_named_inputs() {
+        let dir = env::temp_dir().join(format!("aoc-inputs-{}", process::id()));
+        fs::create_dir_all(dir.join("01")).unwrap();
+        fs::write(dir.join("01.txt"), "default").unwrap();
+        fs::write(dir.join("01").join("bob.txt"), "b").unwrap();
+        fs::write(dir.join("01").join("alice.txt"), "a").unwrap();
+        fs::write(dir.join("01").join("notes.md"), "-").unwrap();
+
+        let inputs = read_inputs_from(&dir, day!(1));
+        let labels: Vec<&str> = inputs.iter().map(|x| x.label()).collect();
+        assert_eq!(labels, ["default", "alice", "bob"]);
+        assert_eq!(inputs[1].data, "a");
+        assert_eq!(read_inputs_from(&dir, day!(2)).is_empty(), true);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,330 @@
+/// Wall-clock and memory limits for solution parts, so runaway solutions can't hang `cargo all`.
+///
+/// Limits are enforced inside the solution process by a watchdog thread. If the first call of a part runs longer
+/// than `--timeout <seconds>` or the process grows beyond `--max-rss <MiB>` while it runs, the watchdog reports the
+/// part as failed, see [`Failure`], and exits the process with [`EXIT_CODE`]. The next process resumes after the
+/// stopped part, see [`ResumeAfter`].
+use std::{
+    env,
+    fmt::Display,
+    process,
+    str::FromStr,
+    sync::{Mutex, Once},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::template::{
+    record::{Failure, PartRecord, PARSE_PART},
+    runner, Day, Year,
+};
+
+/// Exit code of a process that was stopped by the watchdog.
+pub const EXIT_CODE: i32 = 124;
+
+/// How often the watchdog checks the running part.
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Limits for every part of a run, unlimited by default.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Limits {
+    /// Maximum wall-clock time of the first call of a part.
+    pub timeout: Option<Duration>,
+    /// Maximum resident set size of the process while a part runs.
+    pub max_rss_bytes: Option<u64>,
+}
+
+impl Limits {
+    /// Creates limits from a timeout in (fractional) seconds and a maximum resident set size in MiB.
+    pub fn new(timeout_secs: Option<f64>, max_rss_mib: Option<u64>) -> Result<Self, String> {
+        let timeout = timeout_secs
+            .map(|x| {
+                Duration::try_from_secs_f64(x).or(Err(format!("invalid timeout `{x}` seconds.")))
+            })
+            .transpose()?;
+
+        Ok(Limits {
+            timeout,
+            max_rss_bytes: max_rss_mib.map(|x| x * 1024 * 1024),
+        })
+    }
+
+    /// Reads the `--timeout <seconds>` and `--max-rss <MiB>` flags from the process arguments.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        let value = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .and_then(|i| args.get(i + 1))
+        };
+
+        Limits::new(
+            value("--timeout").and_then(|x| x.parse().ok()),
+            value("--max-rss").and_then(|x| x.parse().ok()),
+        )
+        .unwrap_or_default()
+    }
+
+    /// Flags that pass the limits on to a solution process.
+    pub fn to_args(self) -> Vec<String> {
+        let mut args = vec![];
+        if let Some(timeout) = self.timeout {
+            args.extend(["--timeout".into(), timeout.as_secs_f64().to_string()]);
+        }
+        if let Some(max_rss_bytes) = self.max_rss_bytes {
+            args.extend([
+                "--max-rss".into(),
+                (max_rss_bytes / 1024 / 1024).to_string(),
+            ]);
+        }
+        args
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.timeout.is_none() && self.max_rss_bytes.is_none()
+    }
+
+    /// The limit `elapsed` and `rss_bytes` violate, if any.
+    pub fn check(&self, elapsed: Duration, rss_bytes: Option<u64>) -> Option<Failure> {
+        if self.timeout.is_some_and(|timeout| elapsed > timeout) {
+            Some(Failure::TimedOut)
+        } else if self
+            .max_rss_bytes
+            .is_some_and(|max| rss_bytes.is_some_and(|rss| rss > max))
+        {
+            Some(Failure::OutOfMemory)
+        } else {
+            None
+        }
+    }
+}
+
+/// The part that is currently watched.
+#[derive(Clone, Debug)]
+pub struct Watched {
+    pub year: Year,
+    pub day: Day,
+    /// Name of the input, `None` for the default input.
+    pub input: Option<String>,
+    pub part: u8,
+    /// Name of the variant of the part, `None` for the part itself, see [`Variant`](super::solution::Variant).
+    pub variant: Option<String>,
+}
+
+/// The step after which a run continues, because the process of the previous run was stopped by a limit while
+/// running it. Passed as `--resume-after <day>/<input>/<part>[/<variant>]`, with an empty input for the default input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResumeAfter {
+    pub day: Day,
+    /// Name of the input, `None` for the default input.
+    pub input: Option<String>,
+    /// `1` or `2`, or [`PARSE_PART`] if parsing the input was stopped.
+    pub part: u8,
+    pub variant: Option<String>,
+}
+
+impl ResumeAfter {
+    /// Resumes after the part of `record`.
+    pub fn of(record: &PartRecord) -> Self {
+        ResumeAfter {
+            day: record.day,
+            input: record.input.clone(),
+            part: record.part,
+            variant: record.variant.clone(),
+        }
+    }
+
+    /// Reads the `--resume-after` flag from the process arguments.
+    pub fn from_args() -> Option<Self> {
+        let args: Vec<String> = env::args().collect();
+        let i = args.iter().position(|x| x == "--resume-after")?;
+        args.get(i + 1)?.parse().ok()
+    }
+
+    /// Flags that pass the step on to a solution process.
+    pub fn to_args(&self) -> Vec<String> {
+        vec!["--resume-after".into(), self.to_string()]
+    }
+
+    /// How many of `inputs` of the day were already run, in order. A stopped parse step skips the parts of its input.
+    pub fn skipped_inputs(&self, inputs: &[Option<&str>]) -> usize {
+        inputs
+            .iter()
+            .position(|name| *name == self.input.as_deref())
+            .map_or(0, |i| if self.part == PARSE_PART { i + 1 } else { i })
+    }
+
+    /// The steps of input `input_name` of `day` that are left, out of the `(part, variant)` steps in the order they run.
+    pub fn remaining_steps(
+        &self,
+        day: Day,
+        input_name: Option<&str>,
+        steps: &[(u8, Option<&'static str>)],
+    ) -> Vec<(u8, Option<&'static str>)> {
+        if day != self.day || input_name != self.input.as_deref() {
+            return steps.to_vec();
+        }
+        steps
+            .iter()
+            .skip_while(|&&(part, variant)| (part, variant) != (self.part, self.variant.as_deref()))
+            .skip(1)
+            .copied()
+            .collect()
+    }
+}
+
+impl Display for ResumeAfter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let input = self.input.as_deref().unwrap_or_default();
+        write!(f, "{}/{input}/{}", self.day, self.part)?;
+        match &self.variant {
+            Some(variant) => write!(f, "/{variant}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromStr for ResumeAfter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("expected `<day>/<input>/<part>[/<variant>]`, found `{s}`.");
+        let mut fields = s.splitn(4, '/');
+        let mut next = || fields.next().ok_or_else(invalid);
+        let day = next()?.parse().map_err(|_| invalid())?;
+        let input = Some(next()?).filter(|x| !x.is_empty()).map(Into::into);
+        let part = next()?.parse().map_err(|_| invalid())?;
+        let variant = fields.next().map(Into::into);
+
+        Ok(ResumeAfter {
+            day,
+            input,
+            part,
+            variant,
+        })
+    }
+}
+
+static ARMED: Mutex<Option<(Watched, Instant)>> = Mutex::new(None);
+static WATCHDOG: Once = Once::new();
+
+/// Runs `func` while the watchdog enforces the limits passed as process arguments on it.
+pub fn watch<T>(watched: Watched, func: impl FnOnce() -> T) -> T {
+    let limits = Limits::from_args();
+    if limits.is_unlimited() {
+        return func();
+    }
+
+    WATCHDOG.call_once(|| {
+        thread::spawn(move || run_watchdog(limits));
+    });
+
+    *ARMED.lock().unwrap() = Some((watched, Instant::now()));
+    let result = func();
+    *ARMED.lock().unwrap() = None;
+    result
+}
+
+fn run_watchdog(limits: Limits) {
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        let armed = ARMED.lock().unwrap();
+        if let Some((watched, started)) = armed.as_ref() {
+            let elapsed = started.elapsed();
+            let rss_bytes = limits.max_rss_bytes.and_then(|_| current_rss_bytes());
+
+            if let Some(failure) = limits.check(elapsed, rss_bytes) {
+                runner::report_failure(watched, &failure, elapsed);
+                process::exit(EXIT_CODE);
+            }
+        }
+    }
+}
+
+/// Resident set size of the process, read from `/proc/self/statm`. Not available on other platforms.
+#[cfg(target_os = "linux")]
+fn current_rss_bytes() -> Option<u64> {
+    let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+    let pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+    // SAFETY: `sysconf` has no preconditions.
+    let page_size = u64::try_from(unsafe { libc::sysconf(libc::_SC_PAGESIZE) }).ok()?;
+    Some(pages * page_size)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn current_rss_bytes() -> Option<u64> {
+    None
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Limits, ResumeAfter};
+    use crate::day;
+    use crate::template::record::{Failure, PARSE_PART};
+
+    #[test]
+    fn checks_limits() {
+        let limits = Limits::new(Some(1.5), Some(1)).unwrap();
+        assert_eq!(limits.check(Duration::from_secs(1), Some(1024)), None);
+        assert_eq!(
+            limits.check(Duration::from_secs(2), Some(1024)),
+            Some(Failure::TimedOut)
+        );
+        assert_eq!(
+            limits.check(Duration::from_secs(1), Some(2 * 1024 * 1024)),
+            Some(Failure::OutOfMemory)
+        );
+        assert_eq!(Limits::default().check(Duration::MAX, Some(u64::MAX)), None);
+        assert!(Limits::new(Some(-1.0), None).is_err());
+    }
+
+    #[test]
+    fn passes_limits_as_args() {
+        let limits = Limits::new(Some(2.5), Some(512)).unwrap();
+        assert_eq!(limits.to_args(), ["--timeout", "2.5", "--max-rss", "512"]);
+        assert!(Limits::default().to_args().is_empty());
+    }
+
+    #[test]
+    fn passes_resume_point_as_args() {
+        let resume = ResumeAfter {
+            day: day!(21),
+            input: None,
+            part: 2,
+            variant: Some("brute_force".into()),
+        };
+        assert_eq!(resume.to_args(), ["--resume-after", "21//2/brute_force"]);
+        assert_eq!("21//2/brute_force".parse(), Ok(resume));
+
+        let named: ResumeAfter = "05/bob/0".parse().unwrap();
+        assert_eq!(named.input.as_deref(), Some("bob"));
+        assert_eq!(named.variant, None);
+        assert!("05/bob".parse::<ResumeAfter>().is_err());
+    }
+
+    #[test]
+    fn resumes_after_stopped_step() {
+        let resume: ResumeAfter = "08/bob/1".parse().unwrap();
+        let steps = [(1, None), (2, None), (2, Some("crt"))];
+        assert_eq!(
+            resume.remaining_steps(day!(8), Some("bob"), &steps),
+            [(2, None), (2, Some("crt"))]
+        );
+        assert_eq!(resume.remaining_steps(day!(8), None, &steps), steps);
+        assert_eq!(
+            resume.skipped_inputs(&[None, Some("alice"), Some("bob")]),
+            2
+        );
+
+        let parse = ResumeAfter {
+            part: PARSE_PART,
+            ..resume
+        };
+        assert_eq!(parse.skipped_inputs(&[None, Some("alice"), Some("bob")]), 3);
+    }
+}
@@ -1,68 +1,178 @@
-use std::{env, fs};
+use std::fs;
 
-pub mod aoc_cli;
+pub mod aggregate;
+pub mod alloc;
+pub mod answers;
+pub mod aoc_client;
+pub mod cold;
 pub mod commands;
+pub mod complexity;
+pub mod differential;
+pub mod examples;
+pub mod inputs;
+pub mod limits;
+pub mod panics;
+pub mod params;
+pub mod perf;
+pub mod record;
 pub mod runner;
+pub mod solution;
+pub mod stats;
+pub mod threads;
+pub mod variants;
 
 pub use day::*;
+pub use run_multi::RunOptions;
+pub use year::*;
 
+mod compare;
 mod day;
 mod readme_benchmarks;
 mod run_multi;
+mod scaling;
+mod status;
+mod submissions;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+/// Accepts a [`Day`] of the primary year or a `(Year, Day)` tuple, see [`Year::data_dir`].
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+pub fn read_file(folder: &str, day: impl Into<(Year, Day)>) -> String {
+    let (year, day) = day.into();
+    let filepath = year.data_dir().join(folder).join(format!("{day}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
+pub fn read_file_part(folder: &str, day: impl Into<(Year, Day)>, part: u8) -> String {
+    let (year, day) = day.into();
+    let filepath = year
+        .data_dir()
         .join(folder)
         .join(format!("{day}-{part}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+#[cfg(all(feature = "count-alloc", not(feature = "dhat-heap")))]
+#[global_allocator]
+static ALLOC: alloc::CountingAlloc = alloc::CountingAlloc;
+
+#[cfg(all(feature = "count-alloc", feature = "dhat-heap"))]
+compile_error!("the `count-alloc` and `dhat-heap` features both install a global allocator.");
+
+/// Creates the constants `YEAR` and `DAY` and implements [`Solution`](solution::Solution) for the day's `part_one` and
+/// `part_two` functions on a unit struct `Puzzle`. The solution is also registered as `SOLUTION`, so the `aggregate`
+/// binary and other tools can run it in process. Parts return an `Option` or a `Result` of their answer, see
+/// [`IntoAnswer`](solution::IntoAnswer).
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// The day can be followed by these options, separated by commas:
+///  - `1` or `2` to only run a single part of the solution.
+///  - `parse -> Type` for solutions that parse their input into a shared representation. The input is then passed
+///    through a `parse(input: &str) -> Type` function once, and both parts receive a `&Type`.
+///  - `year = 2022` for solutions of a year other than [`Year::PRIMARY`].
+///  - `variants = [crt: 2 => part_two_crt, ...]` for alternative implementations of a part, see
+///    [`Variant`](solution::Variant). They take the same input as the parts and run with `--variant <name>`.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    (@options $day:expr, $year:tt $parse:tt $fns:tt $parts:tt $variants:tt , 1 $($rest:tt)*) => {
+        $crate::solution!(@options $day, $year $parse [part_one, $crate::template::solution::unsolved] [1] $variants $($rest)*);
+    };
+    (@options $day:expr, $year:tt $parse:tt $fns:tt $parts:tt $variants:tt , 2 $($rest:tt)*) => {
+        $crate::solution!(@options $day, $year $parse [$crate::template::solution::unsolved, part_two] [2] $variants $($rest)*);
+    };
+    (@options $day:expr, $year:tt $parse:tt $fns:tt $parts:tt $variants:tt , parse -> $input:ty $(, $($rest:tt)*)?) => {
+        $crate::solution!(@options $day, $year [parse, $input, true] $fns $parts $variants $(, $($rest)*)?);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    (@options $day:expr, $year:tt $parse:tt $fns:tt $parts:tt $variants:tt , year = $y:literal $($rest:tt)*) => {
+        $crate::solution!(@options $day, [$crate::year!($y)] $parse $fns $parts $variants $($rest)*);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    (@options $day:expr, $year:tt $parse:tt $fns:tt $parts:tt $variants:tt , variants = [$( $name:ident : $part:literal => $func:path ),* $(,)?] $($rest:tt)*) => {
+        $crate::solution!(@options $day, $year $parse $fns $parts [$( ($name, $part, $func) ),*] $($rest)*);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@options $day:expr, [$year:expr] [$parse:path, $input:ty, $has_parse:expr] [$part_one:path, $part_two:path] [$( $part:expr ),*] [$( ($variant:ident, $variant_part:literal, $variant_fn:path) ),*]) => {
+        /// The year of the current day.
+        pub const YEAR: $crate::template::Year = $year;
+
         /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+        pub const DAY: $crate::template::Day = $crate::day!($day);
+
+        /// The solution of the current day.
+        pub struct Puzzle;
+
+        impl $crate::template::solution::Solution for Puzzle {
+            const YEAR: $crate::template::Year = YEAR;
+
+            const DAY: $crate::template::Day = DAY;
 
-        #[cfg(feature = "dhat-heap")]
-        #[global_allocator]
-        static ALLOC: dhat::Alloc = dhat::Alloc;
+            const METADATA: $crate::template::solution::Metadata = $crate::template::solution::Metadata {
+                parts: &[$( $part ),*],
+                source: file!(),
+                has_parse: $has_parse,
+                variants: &[$(
+                    $crate::template::solution::Variant { name: stringify!($variant), part: $variant_part }
+                ),*],
+            };
+
+            type Input<'a> = $input;
+
+            fn parse(input: &str) -> Self::Input<'_> {
+                $parse(input)
+            }
+
+            fn part_one(input: &Self::Input<'_>) -> impl $crate::template::solution::IntoAnswer {
+                $part_one(input)
+            }
+
+            fn part_two(input: &Self::Input<'_>) -> impl $crate::template::solution::IntoAnswer {
+                $part_two(input)
+            }
+
+            fn variant(name: &str, part: u8, input: &Self::Input<'_>) -> $crate::template::solution::Answer {
+                $(
+                    if name == stringify!($variant) && part == $variant_part {
+                        return $crate::template::solution::IntoAnswer::into_answer($variant_fn(input));
+                    }
+                )*
+                let _ = input;
+                Err(format!("unknown variant `{name}` of part {part}"))
+            }
+        }
+
+        /// Registration of this solution for the in-process runner.
+        pub const SOLUTION: $crate::template::solution::Registration =
+            $crate::template::solution::Registration::of::<Puzzle>();
 
         fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            $crate::template::threads::configure_from_args();
+            let inputs = $crate::template::inputs::read_inputs((YEAR, DAY));
+            assert!(!inputs.is_empty(), "could not open input file");
+            for input in &inputs {
+                (SOLUTION.run)(&input.data, input.name.as_deref());
+            }
         }
     };
+
+    ($day:expr $(, $($options:tt)*)?) => {
+        $crate::solution!(@options $day,
+            [$crate::template::Year::PRIMARY]
+            [::std::convert::identity, &'a str, false]
+            [part_one, part_two]
+            [1, 2]
+            []
+            $(, $($options)*)?
+        );
+    };
 }
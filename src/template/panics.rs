@@ -0,0 +1,81 @@
+/// Isolates panics of solution parts, so one failing part doesn't take down the rest of a run.
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, Once,
+    },
+};
+
+/// Set while [`catch`] runs a function. Panics outside of it are left to the previous hook.
+static IS_CATCHING: AtomicBool = AtomicBool::new(false);
+/// Message and location of the last panic caught by [`catch`], recorded by the panic hook.
+static LAST_PANIC: Mutex<Option<String>> = Mutex::new(None);
+static HOOK: Once = Once::new();
+
+/// Runs `func` and returns the message of its panic, including where it happened, if it panicked.
+/// Caught panics are not printed, they are reported as a part status instead.
+pub fn catch<T>(func: impl FnOnce() -> T) -> Result<T, String> {
+    HOOK.call_once(install_hook);
+
+    IS_CATCHING.store(true, Ordering::Relaxed);
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    IS_CATCHING.store(false, Ordering::Relaxed);
+
+    result.map_err(|payload| {
+        LAST_PANIC
+            .lock()
+            .unwrap()
+            .take()
+            .unwrap_or_else(|| payload_message(payload.as_ref()))
+    })
+}
+
+fn install_hook() {
+    let previous = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+        if !IS_CATCHING.load(Ordering::Relaxed) {
+            previous(info);
+            return;
+        }
+
+        let message = payload_message(info.payload());
+        let message = match info.location() {
+            Some(location) => format!("{message} at {location}"),
+            None => message,
+        };
+
+        // NOTE: panics of rayon's worker threads are propagated to the caller, keep the first one.
+        LAST_PANIC.lock().unwrap().get_or_insert(message);
+    }));
+}
+
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".into()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::catch;
+
+    #[test]
+    fn catches_panics() {
+        assert_eq!(catch(|| 42), Ok(42));
+
+        let message = catch(|| -> u32 { panic!("invalid input: {}", 7) }).unwrap_err();
+        assert!(message.starts_with("invalid input: 7 at src/template/panics.rs:"));
+
+        let message = catch(|| "x".parse::<u32>().expect("valid number")).unwrap_err();
+        assert!(message.starts_with("valid number: ParseIntError"));
+    }
+}
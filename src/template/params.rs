@@ -0,0 +1,173 @@
+/// Puzzle parameters, so examples and real inputs share one code path.
+///
+/// Some puzzles use different constants for their examples than for the real input, e.g. the number of steps on day
+/// 21. Parts read them with [`get`], passing the value of the real input as the default:
+///
+/// ```ignore
+/// let steps = params::get("steps", 64);
+/// ```
+///
+/// Parameters are set with `cargo solve <day> --param <name>=<value>`, or with `<name> = <value>` lines in the sidecar
+/// of an example, see [`examples`](super::examples).
+use std::{cell::RefCell, collections::BTreeMap, env, str::FromStr, sync::OnceLock};
+
+/// Values of named parameters, as passed on the command line or in a sidecar.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params(BTreeMap<String, String>);
+
+impl Params {
+    /// Reads every `--param <name>=<value>` flag from the process arguments. Invalid flags are ignored.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        args.windows(2)
+            .filter(|pair| pair[0] == "--param")
+            .filter_map(|pair| parse_pair(&pair[1]).ok())
+            .collect()
+    }
+
+    /// Flags that pass the parameters on to a solution process.
+    pub fn to_args(&self) -> Vec<String> {
+        self.0
+            .iter()
+            .flat_map(|(name, value)| ["--param".into(), format!("{name}={value}")])
+            .collect()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Names and values of every parameter, ordered by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for Params {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Params(
+            iter.into_iter()
+                .map(|(name, value)| (name.into(), value.into()))
+                .collect(),
+        )
+    }
+}
+
+impl<const N: usize> From<[(&str, &str); N]> for Params {
+    fn from(value: [(&str, &str); N]) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+/// Parses a `<name>=<value>` pair. Names consist of ASCII letters, digits and underscores.
+pub fn parse_pair(s: &str) -> Result<(String, String), String> {
+    let (name, value) = s
+        .split_once('=')
+        .ok_or(format!("expected `<name>=<value>`, found `{s}`."))?;
+    let name = name.trim();
+
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(format!("invalid parameter name `{name}`."));
+    }
+
+    Ok((name.to_string(), value.trim().to_string()))
+}
+
+thread_local! {
+    /// Parameters set by [`with`] for the current thread.
+    static OVERRIDES: RefCell<Option<Params>> = const { RefCell::new(None) };
+}
+
+static ARGS: OnceLock<Params> = OnceLock::new();
+
+/// The value of the parameter `name`, or `default` if it isn't set.
+/// Values set by [`with`] take precedence over the ones passed as process arguments.
+///
+/// # Panics
+///
+/// Panics if the value can't be parsed as a `T`.
+#[track_caller]
+pub fn get<T: FromStr>(name: &str, default: T) -> T {
+    let value = OVERRIDES.with_borrow(|overrides| {
+        overrides
+            .as_ref()
+            .and_then(|params| params.get(name))
+            .map(String::from)
+    });
+    let value = value.or_else(|| {
+        ARGS.get_or_init(Params::from_args)
+            .get(name)
+            .map(String::from)
+    });
+
+    match value {
+        // NOTE: no closure here, so the panic points at the caller.
+        Some(value) => match value.parse() {
+            Ok(value) => value,
+            Err(_) => panic!("invalid value `{value}` for parameter `{name}`"),
+        },
+        None => default,
+    }
+}
+
+/// Whether any parameter was passed as a process argument. Answers then don't belong to the puzzle of the input.
+pub fn is_overridden() -> bool {
+    !ARGS.get_or_init(Params::from_args).is_empty()
+}
+
+/// Runs `func` with `params` set for the current thread, e.g. to run a part on an example.
+/// Parameters must be read on the thread that calls the part, not on worker threads it spawns.
+pub fn with<T>(params: &Params, func: impl FnOnce() -> T) -> T {
+    /// Restores the previous parameters, even if `func` panics.
+    struct Restore(Option<Params>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            OVERRIDES.set(self.0.take());
+        }
+    }
+
+    let _restore = Restore(OVERRIDES.replace(Some(params.clone())));
+    func()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{get, parse_pair, with, Params};
+
+    #[test]
+    fn parses_pairs() {
+        assert_eq!(parse_pair("steps=6"), Ok(("steps".into(), "6".into())));
+        assert_eq!(
+            parse_pair(" test_area_min = 7 "),
+            Ok(("test_area_min".into(), "7".into()))
+        );
+        assert!(parse_pair("steps").is_err());
+        assert!(parse_pair("1: 42").is_err());
+        assert!(parse_pair("=6").is_err());
+    }
+
+    #[test]
+    fn overrides_defaults() {
+        assert_eq!(get("presses", 1000), 1000);
+
+        let params = Params::from([("presses", "4"), ("steps", "6")]);
+        assert_eq!(with(&params, || get("presses", 1000)), 4);
+        assert_eq!(with(&params, || get::<usize>("steps", 64)), 6);
+        assert_eq!(get("presses", 1000), 1000);
+
+        assert_eq!(
+            params.to_args(),
+            ["--param", "presses=4", "--param", "steps=6"]
+        );
+    }
+}
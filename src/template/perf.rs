@@ -0,0 +1,360 @@
+/// Hardware performance counters, collected through `perf_event_open` on Linux.
+///
+/// Counters are only collected while benching with `--counters`. They count events of the benching thread in user
+/// space, so work that a solution hands off to other threads (e.g. through `rayon`) is not included.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    sync::atomic::{AtomicBool, Ordering},
+};
+use tinyjson::JsonValue;
+
+/// Hardware events per execution of a solution part, averaged over all bench samples.
+/// Events the CPU or kernel can't count are `None`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Counters {
+    pub instructions: Option<f64>,
+    pub cycles: Option<f64>,
+    pub branch_misses: Option<f64>,
+    pub cache_misses: Option<f64>,
+}
+
+/// Whether counters should be collected, i.e. the `--counters` flag was passed.
+pub fn is_enabled() -> bool {
+    std::env::args().any(|x| x == "--counters")
+}
+
+/// Opens the counters if they should be collected. Warns once per process if the system doesn't support them.
+pub fn open_if_enabled() -> Option<CounterGroup> {
+    static HAS_WARNED: AtomicBool = AtomicBool::new(false);
+
+    if !is_enabled() {
+        return None;
+    }
+
+    let group = CounterGroup::open();
+    if group.is_none() && !HAS_WARNED.swap(true, Ordering::Relaxed) {
+        eprintln!(
+            "Hardware counters are not available on this system, check `/proc/sys/kernel/perf_event_paranoid`."
+        );
+    }
+    group
+}
+
+impl Counters {
+    /// Instructions per cycle, if both were counted.
+    pub fn ipc(&self) -> Option<f64> {
+        Some(self.instructions? / self.cycles?).filter(|x| x.is_finite())
+    }
+}
+
+/// Formats a count with a metric suffix, e.g. `1.2M`.
+fn format_count(count: f64) -> String {
+    if count >= 1e9 {
+        format!("{:.1}G", count / 1e9)
+    } else if count >= 1e6 {
+        format!("{:.1}M", count / 1e6)
+    } else if count >= 1e3 {
+        format!("{:.1}k", count / 1e3)
+    } else {
+        format!("{count:.0}")
+    }
+}
+
+impl Display for Counters {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts: Vec<String> = [
+            (self.instructions, "instructions"),
+            (self.cycles, "cycles"),
+            (self.branch_misses, "branch misses"),
+            (self.cache_misses, "cache misses"),
+        ]
+        .into_iter()
+        .filter_map(|(count, label)| Some(format!("{} {label}", format_count(count?))))
+        .collect();
+
+        if let Some(ipc) = self.ipc() {
+            parts.push(format!("{ipc:.2} IPC"));
+        }
+
+        write!(f, "{}", parts.join(" · "))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(target_os = "linux")]
+pub use linux::CounterGroup;
+
+#[cfg(not(target_os = "linux"))]
+pub use unsupported::CounterGroup;
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::{
+        fs::File,
+        io::Read,
+        os::fd::{AsRawFd, FromRawFd},
+    };
+
+    use super::Counters;
+
+    const PERF_TYPE_HARDWARE: u32 = 0;
+    const PERF_COUNT_HW_CPU_CYCLES: u64 = 0;
+    const PERF_COUNT_HW_INSTRUCTIONS: u64 = 1;
+    const PERF_COUNT_HW_CACHE_MISSES: u64 = 3;
+    const PERF_COUNT_HW_BRANCH_MISSES: u64 = 5;
+
+    // bit positions in `PerfEventAttr::flags`.
+    const FLAG_DISABLED: u64 = 1 << 0;
+    const FLAG_EXCLUDE_KERNEL: u64 = 1 << 5;
+    const FLAG_EXCLUDE_HV: u64 = 1 << 6;
+
+    const PERF_EVENT_IOC_ENABLE: libc::c_ulong = 0x2400;
+    const PERF_EVENT_IOC_DISABLE: libc::c_ulong = 0x2401;
+    const PERF_EVENT_IOC_RESET: libc::c_ulong = 0x2403;
+    const PERF_FLAG_FD_CLOEXEC: libc::c_ulong = 1 << 3;
+
+    /// Leading fields of `struct perf_event_attr`, padded to the size of `PERF_ATTR_SIZE_VER7`.
+    #[repr(C)]
+    struct PerfEventAttr {
+        kind: u32,
+        size: u32,
+        config: u64,
+        sample_period: u64,
+        sample_type: u64,
+        read_format: u64,
+        flags: u64,
+        rest: [u8; 80],
+    }
+
+    /// One counter per hardware event, counting the calling thread while enabled.
+    pub struct CounterGroup {
+        instructions: Option<File>,
+        cycles: Option<File>,
+        branch_misses: Option<File>,
+        cache_misses: Option<File>,
+    }
+
+    fn open(config: u64) -> Option<File> {
+        let attr = PerfEventAttr {
+            kind: PERF_TYPE_HARDWARE,
+            size: std::mem::size_of::<PerfEventAttr>() as u32,
+            config,
+            sample_period: 0,
+            sample_type: 0,
+            read_format: 0,
+            flags: FLAG_DISABLED | FLAG_EXCLUDE_KERNEL | FLAG_EXCLUDE_HV,
+            rest: [0; 80],
+        };
+
+        // SAFETY: `attr` is a valid `perf_event_attr` that outlives the call. pid 0 and cpu -1 count the calling
+        // thread on any CPU, a group fd of -1 creates a standalone counter.
+        let fd = unsafe {
+            libc::syscall(
+                libc::SYS_perf_event_open,
+                &attr as *const PerfEventAttr,
+                0,
+                -1,
+                -1,
+                PERF_FLAG_FD_CLOEXEC,
+            )
+        };
+
+        // SAFETY: a non-negative return value is a file descriptor owned by nobody else.
+        (fd >= 0).then(|| unsafe { File::from_raw_fd(fd as i32) })
+    }
+
+    fn ioctl(file: &File, request: libc::c_ulong) {
+        // SAFETY: the perf ioctls used here take no argument and only act on the given counter.
+        unsafe {
+            libc::ioctl(file.as_raw_fd(), request, 0);
+        }
+    }
+
+    impl CounterGroup {
+        /// Opens a counter for every supported event. Returns `None` if no event can be counted, e.g. because
+        /// `perf_event_paranoid` forbids it or the system runs in a VM without a virtual PMU.
+        pub fn open() -> Option<Self> {
+            let group = Self {
+                instructions: open(PERF_COUNT_HW_INSTRUCTIONS),
+                cycles: open(PERF_COUNT_HW_CPU_CYCLES),
+                branch_misses: open(PERF_COUNT_HW_BRANCH_MISSES),
+                cache_misses: open(PERF_COUNT_HW_CACHE_MISSES),
+            };
+            let is_supported = group.files().next().is_some();
+            is_supported.then_some(group)
+        }
+
+        fn files(&self) -> impl Iterator<Item = &File> {
+            [
+                &self.instructions,
+                &self.cycles,
+                &self.branch_misses,
+                &self.cache_misses,
+            ]
+            .into_iter()
+            .flatten()
+        }
+
+        /// Resets and starts all counters.
+        pub fn enable(&self) {
+            for file in self.files() {
+                ioctl(file, PERF_EVENT_IOC_RESET);
+                ioctl(file, PERF_EVENT_IOC_ENABLE);
+            }
+        }
+
+        pub fn disable(&self) {
+            for file in self.files() {
+                ioctl(file, PERF_EVENT_IOC_DISABLE);
+            }
+        }
+
+        /// Reads all counters and divides them by the number of `iterations` they were enabled for.
+        pub fn read(&self, iterations: u128) -> Counters {
+            #[allow(clippy::cast_precision_loss)]
+            let per_iteration = |file: &Option<File>| {
+                let mut buf = [0u8; 8];
+                let mut file = file.as_ref()?;
+                file.read_exact(&mut buf).ok()?;
+                Some(u64::from_ne_bytes(buf) as f64 / iterations.max(1) as f64)
+            };
+
+            Counters {
+                instructions: per_iteration(&self.instructions),
+                cycles: per_iteration(&self.cycles),
+                branch_misses: per_iteration(&self.branch_misses),
+                cache_misses: per_iteration(&self.cache_misses),
+            }
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod unsupported {
+    use super::Counters;
+
+    /// Hardware counters are only supported on Linux.
+    pub struct CounterGroup;
+
+    impl CounterGroup {
+        pub fn open() -> Option<Self> {
+            None
+        }
+
+        pub fn enable(&self) {}
+
+        pub fn disable(&self) {}
+
+        pub fn read(&self, _iterations: u128) -> Counters {
+            Counters::default()
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+const KEYS: [&str; 4] = ["instructions", "cycles", "branch_misses", "cache_misses"];
+
+fn optional(value: Option<f64>) -> JsonValue {
+    value.map_or(JsonValue::Null, JsonValue::Number)
+}
+
+impl Counters {
+    fn values(&self) -> [Option<f64>; 4] {
+        [
+            self.instructions,
+            self.cycles,
+            self.branch_misses,
+            self.cache_misses,
+        ]
+    }
+
+    /// Writes the counters as flat keys into a JSON object.
+    pub(crate) fn insert_into(&self, map: &mut HashMap<String, JsonValue>) {
+        for (key, value) in KEYS.iter().zip(self.values()) {
+            map.insert((*key).into(), optional(value));
+        }
+    }
+
+    /// Reads counters written by [`Counters::insert_into`] from a JSON object.
+    /// Objects without any counter keys yield `None`.
+    pub(crate) fn read_from(map: &HashMap<String, JsonValue>) -> Result<Option<Self>, String> {
+        if !KEYS.iter().any(|key| map.contains_key(*key)) {
+            return Ok(None);
+        }
+
+        let number = |key: &str| match map.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => v
+                .get::<f64>()
+                .copied()
+                .map(Some)
+                .ok_or(format!("Expected `{key}` to be null or a number.")),
+        };
+
+        Ok(Some(Self {
+            instructions: number(KEYS[0])?,
+            cycles: number(KEYS[1])?,
+            branch_misses: number(KEYS[2])?,
+            cache_misses: number(KEYS[3])?,
+        }))
+    }
+}
+
+impl From<&Counters> for JsonValue {
+    fn from(value: &Counters) -> Self {
+        let mut map = HashMap::new();
+        value.insert_into(&mut map);
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Counters {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected counters to be a JSON object.")?;
+        Ok(Counters::read_from(json)?.unwrap_or_default())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::Counters;
+
+    fn get_mock_counters() -> Counters {
+        Counters {
+            instructions: Some(1_200_000.0),
+            cycles: Some(800_000.0),
+            branch_misses: Some(3_400.0),
+            cache_misses: None,
+        }
+    }
+
+    #[test]
+    fn formats_counters() {
+        assert_eq!(
+            get_mock_counters().to_string(),
+            "1.2M instructions · 800.0k cycles · 3.4k branch misses · 1.50 IPC"
+        );
+        assert_eq!(Counters::default().to_string(), "");
+    }
+
+    #[test]
+    fn roundtrips_counters() {
+        let counters = get_mock_counters();
+        let mut map = HashMap::new();
+        counters.insert_into(&mut map);
+        assert_eq!(Counters::read_from(&map), Ok(Some(counters)));
+        assert_eq!(Counters::read_from(&HashMap::new()), Ok(None));
+    }
+}
@@ -1,18 +1,37 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{fmt::Display, fs, io};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::alloc::format_bytes;
+use crate::template::timings::{PartTiming, Timing, Timings};
+use crate::template::{Day, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
+/// Marker around the table of `year`. Years other than the primary year get their own table.
+fn marker(year: Year) -> String {
+    if year.is_primary() {
+        MARKER.into()
+    } else {
+        format!("<!--- benchmarking table {year} --->")
+    }
+}
+
 #[derive(Debug)]
 pub enum Error {
     Parser(String),
     IO(io::Error),
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
@@ -25,12 +44,12 @@ pub struct TablePosition {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(year: Year, day: Day) -> String {
+    format!("./src/bin/{}.rs", year.bin_name(day))
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -51,76 +70,145 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn format_part(part: Option<&PartTiming>) -> String {
+    part.map_or_else(
+        || "-".into(),
+        |p| match &p.failure {
+            Some(failure) => format!("{failure} after {:.1?}", p.duration()),
+            None => format!("{:.1?}", p.duration()),
+        },
+    )
+}
+
+/// Total allocations and highest peak heap usage over all steps of a day, if they were recorded.
+fn format_alloc(timing: &Timing) -> String {
+    let allocs: Vec<_> = [&timing.parse, &timing.part_1, &timing.part_2]
+        .into_iter()
+        .filter_map(|part| part.as_ref()?.alloc)
+        .collect();
+
+    if allocs.is_empty() {
+        return "-".into();
+    }
+
+    let allocations: u64 = allocs.iter().map(|x| x.allocations).sum();
+    let peak = allocs.iter().map(|x| x.peak_bytes).max().unwrap_or(0);
+    format!("`{allocations} · {} peak`", format_bytes(peak))
+}
+
+fn construct_table(prefix: &str, year: Year, timings: Timings, total_millis: f64) -> String {
+    let header = if year.is_primary() {
+        format!("{prefix} Benchmarks")
+    } else {
+        format!("{prefix} {year} Benchmarks")
+    };
+
+    // NOTE: the allocations column is only shown once allocations were recorded with `cargo time --alloc`.
+    let has_alloc = timings.data.iter().any(|t| format_alloc(t) != "-");
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let mut lines: Vec<String> = vec![marker(year), header, String::new()];
+
+    if has_alloc {
+        lines.push("| Day | Part 1 | Part 2 | Allocations |".into());
+        lines.push("| :---: | :---: | :---:  | :---: |".into());
+    } else {
+        lines.push("| Day | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---:  |".into());
+    }
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
+        let path = get_path_for_bin(year, timing.day);
+        let mut line = format!(
             "| [Day {}]({}) | `{}` | `{}` |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+            format_part(timing.part_1.as_ref()),
+            format_part(timing.part_2.as_ref())
+        );
+        if has_alloc {
+            line.push_str(&format!(" {} |", format_alloc(&timing)));
+        }
+        lines.push(line);
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker(year));
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+) -> Result<(), Error> {
+    let marker = marker(year);
+
+    // NOTE: tables of other years are added below the primary table the first time they are benched.
+    if !year.is_primary() && !s.contains(&marker) {
+        let pos_end = locate_table(s, MARKER)?.pos_end;
+        s.insert_str(pos_end, &format!("\n\n{marker}{marker}"));
+    }
+
+    let positions = locate_table(s, &marker)?;
+    let table = construct_table("##", year, timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+pub fn update(year: Year, timings: Timings) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, year, timings, total_millis)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
+    use std::time::Duration;
+
     use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{
+        day,
+        template::alloc::AllocStats,
+        template::timings::{Timing, Timings},
+        template::Year,
+        year,
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    parse: None,
+                    part_1: Some(Duration::from_millis(10).into()),
+                    part_2: Some(Duration::from_millis(20).into()),
                     total_nanos: 3e+10,
+                    commit: None,
+                    toolchain: None,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    parse: None,
+                    part_1: Some(Duration::from_millis(30).into()),
+                    part_2: Some(Duration::from_millis(40).into()),
                     total_nanos: 7e+10,
+                    commit: None,
+                    toolchain: None,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
+                    parse: None,
+                    part_1: Some(Duration::from_millis(40).into()),
+                    part_2: Some(Duration::from_millis(50).into()),
                     total_nanos: 9e+10,
+                    commit: None,
+                    toolchain: None,
                 },
             ],
         }
@@ -130,28 +218,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::PRIMARY, get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::PRIMARY, get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::PRIMARY, get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::PRIMARY, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::PRIMARY, get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -159,7 +247,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::PRIMARY, get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -168,9 +256,9 @@ mod tests {
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -179,4 +267,40 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn adds_tables_for_other_years() {
+        let other = year!(2015);
+        let mut s = format!("foo\n{}{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, Year::PRIMARY, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, other, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, other, get_mock_timings(), 190.0).unwrap();
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert_eq!(s.matches("## 2015 Benchmarks").count(), 1);
+        assert_eq!(s.contains("| [Day 1](./src/bin/2015-01.rs) |"), true);
+        assert_eq!(s.ends_with("<!--- benchmarking table 2015 --->\nbaz"), true);
+        assert!(s.find("## Benchmarks").unwrap() < s.find("## 2015 Benchmarks").unwrap());
+    }
+
+    #[test]
+    fn adds_allocation_column() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_2.as_mut().unwrap().alloc = Some(AllocStats {
+            allocations: 12,
+            bytes: 8192,
+            peak_bytes: 2048,
+        });
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, Year::PRIMARY, timings, 190.0).unwrap();
+        assert_eq!(s.contains("| Day | Part 1 | Part 2 | Allocations |"), true);
+        assert_eq!(
+            s.contains("| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` | `12 · 2.0 KiB peak` |"),
+            true
+        );
+        assert_eq!(
+            s.contains("| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` | - |"),
+            true
+        );
+    }
 }
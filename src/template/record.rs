@@ -0,0 +1,517 @@
+/// Structured output of solution runs, used by `--format json`.
+use std::{collections::HashMap, fmt::Display, str::FromStr, time::Duration};
+use tinyjson::JsonValue;
+
+use crate::template::{alloc::AllocStats, perf::Counters, stats::Stats, Day};
+
+/// How a solution binary reports its results on stdout.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human readable output with ANSI styling and a progress indicator.
+    #[default]
+    Human,
+    /// One JSON object per line, see [`PartRecord`].
+    Json,
+}
+
+impl OutputFormat {
+    /// Reads the `--format <human|json>` flag from the process arguments.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+
+        args.iter()
+            .position(|x| x == "--format")
+            .and_then(|i| args.get(i + 1))
+            .and_then(|x| x.parse().ok())
+            .unwrap_or_default()
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(OutputFormat::Human),
+            "json" => Ok(OutputFormat::Json),
+            x => Err(format!(
+                "unknown output format `{x}`, expecting `human` or `json`."
+            )),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Value of [`PartRecord::part`] for the parse step of solutions that declare one.
+pub const PARSE_PART: u8 = 0;
+
+/// Human readable name of a part, e.g. `Part 1` or `Parse`.
+pub fn part_label(part: u8) -> String {
+    if part == PARSE_PART {
+        "Parse".into()
+    } else {
+        format!("Part {part}")
+    }
+}
+
+/// Why a part stopped without an answer, other than returning none.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Failure {
+    /// The part ran longer than the `--timeout` limit, see [`limits`](super::limits).
+    TimedOut,
+    /// The process grew beyond the `--max-rss` limit while the part ran.
+    OutOfMemory,
+    /// The part panicked, with the panic message and its location, see [`panics`](super::panics).
+    Panicked(String),
+    /// The part returned an error, with the chain of its sources, see [`IntoAnswer`](super::solution::IntoAnswer).
+    Errored(String),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Failure::TimedOut => "timed out",
+            Failure::OutOfMemory => "out of memory",
+            Failure::Panicked(_) => "panicked",
+            Failure::Errored(_) => "error",
+        })
+    }
+}
+
+impl Failure {
+    /// Key of the failure in JSON records and timings.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Failure::TimedOut => "timeout",
+            Failure::OutOfMemory => "oom",
+            Failure::Panicked(_) => "panic",
+            Failure::Errored(_) => "error",
+        }
+    }
+
+    /// The panic message or error chain of the failure.
+    pub fn message(&self) -> Option<&str> {
+        match self {
+            Failure::Panicked(message) | Failure::Errored(message) => Some(message),
+            Failure::TimedOut | Failure::OutOfMemory => None,
+        }
+    }
+
+    /// Whether the failure is a violated limit. Those end the solution process, while panics and errors don't.
+    pub fn is_limit(&self) -> bool {
+        matches!(self, Failure::TimedOut | Failure::OutOfMemory)
+    }
+
+    /// Inserts the failure as flat keys into a JSON object, see [`Failure::read_from`].
+    pub fn insert_into(&self, map: &mut HashMap<String, JsonValue>) {
+        map.insert("failure".into(), JsonValue::String(self.key().into()));
+        if let Some(message) = self.message() {
+            map.insert("failure_message".into(), JsonValue::String(message.into()));
+        }
+    }
+
+    /// Reads a failure from the flat keys of a JSON object, `None` if the object has no or a null `failure` key.
+    pub fn read_from(json: &HashMap<String, JsonValue>) -> Result<Option<Self>, String> {
+        let failure = match json.get("failure") {
+            None => return Ok(None),
+            Some(v) if v.is_null() => return Ok(None),
+            Some(v) => v
+                .get::<String>()
+                .ok_or("Expected failure to be a string.")?
+                .parse::<Failure>()?,
+        };
+
+        let message = json
+            .get("failure_message")
+            .map(|v| {
+                v.get::<String>()
+                    .ok_or("Expected failure_message to be a string.")
+            })
+            .transpose()?
+            .cloned()
+            .unwrap_or_default();
+
+        Ok(Some(match failure {
+            Failure::Panicked(_) => Failure::Panicked(message),
+            Failure::Errored(_) => Failure::Errored(message),
+            failure => failure,
+        }))
+    }
+}
+
+impl FromStr for Failure {
+    type Err = String;
+
+    /// Parses the key of a failure. Panics and errors are parsed without their message, see [`Failure::read_from`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "timeout" => Ok(Failure::TimedOut),
+            "oom" => Ok(Failure::OutOfMemory),
+            "panic" => Ok(Failure::Panicked(String::new())),
+            "error" => Ok(Failure::Errored(String::new())),
+            x => Err(format!(
+                "unknown failure `{x}`, expecting `timeout`, `oom`, `panic` or `error`."
+            )),
+        }
+    }
+}
+
+/// The result of running a single part of a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartRecord {
+    pub day: Day,
+    /// Name of the input the part ran on, `None` for the default input.
+    pub input: Option<String>,
+    /// `1` or `2`, or [`PARSE_PART`] for the time spent parsing the input.
+    pub part: u8,
+    /// Name of the variant that ran instead of the part, see [`Variant`](super::solution::Variant).
+    pub variant: Option<String>,
+    pub answer: Option<String>,
+    pub stats: Stats,
+    /// Heap usage of a single run, only recorded with `--alloc`.
+    pub alloc: Option<AllocStats>,
+    /// Hardware counters per run, only collected while benching with `--counters`.
+    pub counters: Option<Counters>,
+    /// Set if the part failed or was stopped before it returned, `stats` then holds the time until then.
+    pub failure: Option<Failure>,
+}
+
+impl PartRecord {
+    /// Serializes the record to a single line of JSON.
+    pub fn to_json_line(&self) -> String {
+        // NOTE: serializing our own object can not fail.
+        JsonValue::from(self).stringify().unwrap()
+    }
+}
+
+impl From<&PartRecord> for JsonValue {
+    fn from(value: &PartRecord) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        if let Some(variant) = &value.variant {
+            map.insert("variant".into(), JsonValue::String(variant.clone()));
+        }
+        if let Some(input) = &value.input {
+            map.insert("input".into(), JsonValue::String(input.clone()));
+        }
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        value.stats.insert_into(&mut map);
+        if let Some(alloc) = &value.alloc {
+            alloc.insert_into(&mut map);
+        }
+        if let Some(counters) = &value.counters {
+            counters.insert_into(&mut map);
+        }
+        if let Some(failure) = &value.failure {
+            failure.insert_into(&mut map);
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartRecord {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected record.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .filter(|x| [0.0, 1.0, 2.0].contains(*x))
+            .ok_or("Expected record.part to be 0, 1 or 2.")?;
+
+        let input = json
+            .get("input")
+            .map(|v| {
+                v.get::<String>()
+                    .ok_or("Expected record.input to be a string.")
+            })
+            .transpose()?;
+
+        let variant = json
+            .get("variant")
+            .map(|v| {
+                v.get::<String>()
+                    .ok_or("Expected record.variant to be a string.")
+            })
+            .transpose()?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected record.answer to be null or string.")?;
+
+        let stats = Stats::read_from(json).map_err(|e| format!("Invalid record: {e}"))?;
+        let alloc = AllocStats::read_from(json).map_err(|e| format!("Invalid record: {e}"))?;
+        let counters = Counters::read_from(json).map_err(|e| format!("Invalid record: {e}"))?;
+
+        let failure = Failure::read_from(json).map_err(|e| format!("Invalid record: {e}"))?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(PartRecord {
+            day,
+            input: input.cloned(),
+            part: *part as u8,
+            variant: variant.cloned(),
+            answer: answer.cloned(),
+            stats,
+            alloc,
+            counters,
+            failure,
+        })
+    }
+}
+
+impl FromStr for PartRecord {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("not a valid JSON record."))?;
+        PartRecord::try_from(&json)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Emitted once after all solutions of an in-process run have finished.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RunSummary {
+    /// End-to-end time it took to read all inputs and solve all parts once.
+    pub wall: Duration,
+}
+
+impl RunSummary {
+    /// Serializes the summary to a single line of JSON.
+    pub fn to_json_line(&self) -> String {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "wall_nanos".into(),
+            JsonValue::Number(self.wall.as_nanos() as f64),
+        );
+        // NOTE: serializing our own object can not fail.
+        JsonValue::Object(map).stringify().unwrap()
+    }
+}
+
+impl FromStr for RunSummary {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("not a valid JSON summary."))?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        json.get::<HashMap<String, JsonValue>>()
+            .and_then(|map| map.get("wall_nanos"))
+            .and_then(|v| v.get::<f64>())
+            .map(|x| RunSummary {
+                wall: Duration::from_nanos(*x as u64),
+            })
+            .ok_or("Expected summary.wall_nanos to be a number.".into())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{part_label, Failure, OutputFormat, PartRecord, RunSummary, PARSE_PART};
+    use crate::{day, template::stats::Stats};
+
+    #[test]
+    fn roundtrips_records() {
+        let record = PartRecord {
+            day: day!(6),
+            input: None,
+            part: 2,
+            variant: None,
+            answer: Some("line 1\nline \"2\"".into()),
+            stats: Stats::from_samples(&mut [100, 102, 99, 180].map(Duration::from_nanos)),
+            alloc: None,
+            counters: None,
+            failure: None,
+        };
+        let parsed: PartRecord = record.to_json_line().parse().unwrap();
+        assert_eq!(parsed, record);
+    }
+
+    #[test]
+    fn roundtrips_missing_answers() {
+        let record = PartRecord {
+            day: day!(25),
+            input: None,
+            part: 1,
+            variant: None,
+            answer: None,
+            stats: Stats::single(Duration::from_millis(3)),
+            alloc: None,
+            counters: None,
+            failure: None,
+        };
+        let parsed: PartRecord = record.to_json_line().parse().unwrap();
+        assert_eq!(parsed, record);
+    }
+
+    #[test]
+    fn roundtrips_parse_records() {
+        let record = PartRecord {
+            day: day!(17),
+            input: None,
+            part: PARSE_PART,
+            variant: None,
+            answer: None,
+            stats: Stats::single(Duration::from_micros(12)),
+            alloc: None,
+            counters: None,
+            failure: None,
+        };
+        let parsed: PartRecord = record.to_json_line().parse().unwrap();
+        assert_eq!(parsed, record);
+        assert_eq!(part_label(parsed.part), "Parse");
+    }
+
+    #[test]
+    fn roundtrips_named_inputs() {
+        let record = PartRecord {
+            day: day!(21),
+            input: Some("alice".into()),
+            part: 2,
+            variant: None,
+            answer: Some("42".into()),
+            stats: Stats::single(Duration::from_micros(5)),
+            alloc: None,
+            counters: None,
+            failure: None,
+        };
+        let parsed: PartRecord = record.to_json_line().parse().unwrap();
+        assert_eq!(parsed, record);
+    }
+
+    #[test]
+    fn roundtrips_variants() {
+        let record = PartRecord {
+            day: day!(8),
+            input: None,
+            part: 2,
+            variant: Some("crt".into()),
+            answer: Some("6".into()),
+            stats: Stats::single(Duration::from_micros(5)),
+            alloc: None,
+            counters: None,
+            failure: None,
+        };
+        let parsed: PartRecord = record.to_json_line().parse().unwrap();
+        assert_eq!(parsed, record);
+    }
+
+    #[test]
+    fn roundtrips_failures() {
+        let record = PartRecord {
+            day: day!(21),
+            input: None,
+            part: 2,
+            variant: None,
+            answer: None,
+            stats: Stats::single(Duration::from_secs(10)),
+            alloc: None,
+            counters: None,
+            failure: Some(Failure::TimedOut),
+        };
+        let parsed: PartRecord = record.to_json_line().parse().unwrap();
+        assert_eq!(parsed, record);
+        assert!("segfault".parse::<Failure>().is_err());
+
+        let record = PartRecord {
+            part: PARSE_PART,
+            stats: Stats::single(Duration::from_micros(3)),
+            failure: Some(Failure::Panicked(
+                "valid input: ParseIntError { kind: InvalidDigit } at src/bin/10.rs:12:5".into(),
+            )),
+            ..record
+        };
+        let parsed: PartRecord = record.to_json_line().parse().unwrap();
+        assert_eq!(parsed, record);
+
+        let record = PartRecord {
+            part: 1,
+            failure: Some(Failure::Errored("Q is not a valid tile character".into())),
+            ..record
+        };
+        let parsed: PartRecord = record.to_json_line().parse().unwrap();
+        assert_eq!(parsed, record);
+    }
+
+    #[test]
+    fn emits_single_lines() {
+        let record = PartRecord {
+            day: day!(1),
+            input: None,
+            part: 1,
+            variant: None,
+            answer: Some("a\nb".into()),
+            stats: Stats::single(Duration::from_nanos(1)),
+            alloc: None,
+            counters: None,
+            failure: None,
+        };
+        assert_eq!(record.to_json_line().lines().count(), 1);
+    }
+
+    #[test]
+    fn rejects_invalid_records() {
+        assert!("Part 1: 0 (74.13ns @ 100000 samples)"
+            .parse::<PartRecord>()
+            .is_err());
+        assert!(
+            r#"{ "day": "01", "part": 3, "answer": null, "duration_nanos": 1, "samples": 1 }"#
+                .parse::<PartRecord>()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn requires_stats() {
+        assert!(
+            r#"{ "day": "01", "part": 1, "answer": null, "duration_nanos": 1, "samples": 1 }"#
+                .parse::<PartRecord>()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn roundtrips_summaries() {
+        let summary = RunSummary {
+            wall: Duration::from_micros(30_640),
+        };
+        assert_eq!(summary.to_json_line().parse(), Ok(summary));
+        assert!(r#"{ "day": "01" }"#.parse::<RunSummary>().is_err());
+    }
+
+    #[test]
+    fn parses_output_formats() {
+        assert_eq!("json".parse(), Ok(OutputFormat::Json));
+        assert_eq!("human".parse(), Ok(OutputFormat::Human));
+        assert!("xml".parse::<OutputFormat>().is_err());
+    }
+}
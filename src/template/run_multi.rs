@@ -1,21 +1,70 @@
-use std::{collections::HashSet, io};
-
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use std::{collections::HashSet, fmt::Display, io, process::ExitStatus, time::Duration};
+
+use crate::template::{
+    limits::Limits,
+    record::{part_label, PARSE_PART},
+    runner::{print_combined, print_input_header},
+    status::print_status_table,
+    Day, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
 use super::{
     all_days,
-    timings::{Timing, Timings},
+    timings::{current_commit, current_toolchain, Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// How the `aggregate` binary builds and runs the solutions.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RunOptions {
+    pub is_release: bool,
+    /// Bench every part and report timings.
+    pub is_timed: bool,
+    /// Build with a counting allocator and report the heap usage of every part.
+    pub is_alloc: bool,
+    /// Collect hardware counters while benching.
+    pub is_counters: bool,
+    /// Size of rayon's global thread pool, rayon's default if `None`.
+    pub threads: Option<usize>,
+    /// Limits for every part, see [`limits`](super::limits).
+    pub limits: Limits,
+    /// Run every variant next to its part, only for days that have variants, see [`variants`](super::variants).
+    pub is_variants: bool,
+}
 
-    let mut need_space = false;
+/// What [`run_multi`] reported.
+#[derive(Debug)]
+pub struct MultiRun {
+    /// Timings of every day that ran, if the run was timed.
+    pub timings: Option<Timings>,
+    /// Number of parts that failed.
+    pub failures: usize,
+    /// Why the `aggregate` binary stopped early, if it did. The records it reported before are kept.
+    pub error: Option<Error>,
+}
 
+impl MultiRun {
+    /// Whether every part ran without failing.
+    pub fn is_ok(&self) -> bool {
+        self.failures == 0 && self.error.is_none()
+    }
+}
+
+pub fn run_multi(year: Year, days_to_run: &HashSet<Day>, options: &RunOptions) -> MultiRun {
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+
+    let mut pending = days.iter().copied().peekable();
+    let mut need_space = false;
+    // time spent on the current day, if it declares a parse step.
+    let mut combined: Option<Duration> = None;
+
+    // records arrive ordered by day, print headers for every day up to the current record.
+    let mut print_headers_until = |until: Option<Day>, combined: &mut Option<Duration>| {
+        while let Some(day) = pending.next_if(|day| until.is_none_or(|until| *day <= until)) {
+            if let Some(combined) = combined.take() {
+                print_combined(&combined);
+            }
+
             if need_space {
                 println!();
             }
@@ -24,25 +73,141 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
-
-            if output.is_empty() {
+            if Some(day) != until {
                 println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
             }
-        });
+        }
+
+        if until.is_none() {
+            if let Some(combined) = combined.take() {
+                print_combined(&combined);
+            }
+        }
+    };
+
+    // day and input of the previous record, named inputs get a header of their own.
+    let mut current: Option<(Day, Option<String>)> = None;
+    // records already streamed, kept if the run fails later.
+    let mut streamed = vec![];
+
+    let output = child_commands::run_solutions(year, &days, options, |record| {
+        streamed.push(record.clone());
+
+        let is_new_day = current.as_ref().is_none_or(|(day, _)| *day != record.day);
+        let is_new_input = current
+            .as_ref()
+            .is_none_or(|(_, input)| *input != record.input);
+
+        print_headers_until(Some(record.day), &mut combined);
+
+        if is_new_input {
+            if !is_new_day {
+                if let Some(combined) = combined.take() {
+                    print_combined(&combined);
+                }
+            }
+            if let Some(name) = &record.input {
+                print_input_header(name);
+            }
+        }
+        current = Some((record.day, record.input.clone()));
+
+        child_commands::print_record(year, record);
+        if record.failure.is_some() {
+            // NOTE: the combined time of a day with a failed part would be misleading.
+            combined = None;
+        } else if record.part == PARSE_PART {
+            combined = Some(record.stats.mean);
+        } else if let Some(combined) = &mut combined {
+            *combined += record.stats.mean;
+        }
+    });
+    print_headers_until(None, &mut combined);
+
+    let (output, error) = match output {
+        Ok(output) => (output, None),
+        Err(e) => {
+            eprintln!("Failed to run solutions: {e}");
+            let output = child_commands::Output {
+                records: streamed,
+                summary: None,
+            };
+            (output, Some(e))
+        }
+    };
+
+    let mut timings: Vec<Timing> = days
+        .iter()
+        .filter(|day| output.records.iter().any(|r| r.day == **day))
+        .map(|day| child_commands::records_to_timing(&output.records, *day))
+        .collect();
+
+    let failures: Vec<String> = output
+        .records
+        .iter()
+        .filter_map(|record| {
+            let input = record
+                .input
+                .as_ref()
+                .map_or_else(String::new, |name| format!(" ({name})"));
+            let failure = record.failure.as_ref()?;
+            let message = failure
+                .message()
+                .map_or_else(String::new, |message| format!(": {message}"));
+            Some(format!(
+                "Day {} {}{input}: {failure} after {:.1?}{message}",
+                record.day,
+                part_label(record.part),
+                record.stats.mean
+            ))
+        })
+        .collect();
+
+    print_status_table(year, &days, &output.records);
+
+    if !failures.is_empty() {
+        println!("\n{ANSI_BOLD}Failures{ANSI_RESET}");
+        println!("------");
+        for failure in &failures {
+            println!("{failure}");
+        }
+    }
+
+    let wall_str = output.summary.map(|summary| {
+        let wall_millis = summary.wall.as_secs_f64() * 1000.0;
+        format!("{ANSI_BOLD}Total (Wall):{ANSI_RESET} {ANSI_ITALIC}{wall_millis:.2}ms{ANSI_RESET}")
+    });
+
+    let failures = failures.len();
+    let timings = if options.is_timed {
+        // NOTE: remember what the timings were measured on, so they can be compared meaningfully later.
+        let commit = current_commit();
+        let toolchain = current_toolchain();
+        for timing in &mut timings {
+            timing.commit.clone_from(&commit);
+            timing.toolchain.clone_from(&toolchain);
+        }
 
-    if is_timed {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
+        if let Some(wall_str) = wall_str {
+            println!("{wall_str}");
+        }
         Some(timings)
     } else {
+        if let Some(wall_str) = wall_str {
+            println!("\n{wall_str}");
+        }
         None
+    };
+
+    MultiRun {
+        timings,
+        failures,
+        error,
     }
 }
 
@@ -50,6 +215,17 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
 pub enum Error {
     BrokenPipe,
     IO(io::Error),
+    Status(ExitStatus),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "could not read output of child process."),
+            Error::IO(e) => write!(f, "{e}"),
+            Error::Status(status) => write!(f, "child process failed with {status}."),
+        }
+    }
 }
 
 impl From<std::io::Error> for Error {
@@ -58,45 +234,134 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// All solutions are linked into the `aggregate` binary, which runs them in a single process.
+/// This module encapsulates interaction with this binary, both invoking it as well as collecting the records it emits.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{Error, RunOptions};
+    use crate::template::{
+        limits::{self, ResumeAfter},
+        record::{part_label, Failure, PartRecord, RunSummary, PARSE_PART},
+        runner::{
+            format_duration, format_status, print_alloc, print_counters, print_failure,
+            print_result, print_stats,
+        },
+        timings::PartTiming,
+        Day, Year,
+    };
     use std::{
         io::{BufRead, BufReader},
-        path::Path,
         process::{Command, Stdio},
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    /// Everything the `aggregate` binary reported.
+    #[derive(Debug, Default)]
+    pub struct Output {
+        pub records: Vec<PartRecord>,
+        pub summary: Option<RunSummary>,
+    }
+
+    /// Run the solutions for the given days of `year` in process, calling `on_record` as soon as a part finishes.
+    /// If a part is stopped by a limit, the process exits and a new one resumes with the next part or input of its day,
+    /// see [`ResumeAfter`].
+    /// Panics are caught by the process itself, see [`panics`](crate::template::panics).
+    pub fn run_solutions(
+        year: Year,
+        days: &[Day],
+        options: &RunOptions,
+        mut on_record: impl FnMut(&PartRecord),
+    ) -> Result<Output, Error> {
+        let mut output = Output::default();
+        let mut remaining = days.to_vec();
+        let mut resume: Option<ResumeAfter> = None;
+
+        while !remaining.is_empty() {
+            let records_before = output.records.len();
+            run_aggregate(
+                year,
+                &remaining,
+                resume.as_ref(),
+                options,
+                &mut on_record,
+                &mut output,
+            )?;
+
+            match output.records[records_before..].last() {
+                Some(last) if last.failure.as_ref().is_some_and(Failure::is_limit) => {
+                    let failed_day = last.day;
+                    remaining.retain(|day| *day >= failed_day);
+                    resume = Some(ResumeAfter::of(last));
+                }
+                _ => break,
+            }
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        if resume.is_some() {
+            // NOTE: the wall clock of a single process only covers some of the days.
+            output.summary = None;
+        }
+
+        Ok(output)
+    }
 
-        if is_release {
-            args.push("--release");
+    fn run_aggregate(
+        year: Year,
+        days: &[Day],
+        resume: Option<&ResumeAfter>,
+        options: &RunOptions,
+        on_record: &mut impl FnMut(&PartRecord),
+        output: &mut Output,
+    ) -> Result<(), Error> {
+        let mut args = vec![
+            "run".to_string(),
+            "--quiet".to_string(),
+            "--bin".to_string(),
+            "aggregate".to_string(),
+        ];
+
+        if options.is_release {
+            args.push("--release".to_string());
         }
 
-        if is_timed {
+        if options.is_alloc {
+            args.extend(["--features", "count-alloc"].map(String::from));
+        }
+
+        // ask the child for machine-readable output.
+        args.extend(["--", "--format", "json"].map(String::from));
+        args.extend(["--year".to_string(), year.to_string()]);
+
+        if options.is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+            args.push("--time".to_string());
         }
 
+        if options.is_alloc {
+            args.push("--alloc".to_string());
+        }
+
+        if options.is_counters {
+            args.push("--counters".to_string());
+        }
+
+        if options.is_variants {
+            args.push("--variants".to_string());
+        }
+
+        if let Some(threads) = options.threads {
+            args.extend(["--threads".to_string(), threads.to_string()]);
+        }
+
+        args.extend(options.limits.to_args());
+
+        if let Some(resume) = resume {
+            args.extend(resume.to_args());
+        }
+
+        args.extend(days.iter().map(Day::to_string));
+
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward stderr while collecting records from stdout.
 
         let mut cmd = Command::new("cargo")
             .args(&args)
@@ -107,8 +372,6 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
-
         let thread = thread::spawn(move || {
             stderr.lines().for_each(|line| {
                 eprintln!("{}", line.unwrap());
@@ -117,140 +380,193 @@ pub mod child_commands {
 
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            if let Ok(record) = line.parse::<PartRecord>() {
+                // NOTE: a resumed run parses the input of the stopped part again, which was already reported.
+                if output.records.iter().any(|r| is_same_step(r, &record)) {
+                    continue;
+                }
+                on_record(&record);
+                output.records.push(record);
+            } else if let Ok(summary) = line.parse::<RunSummary>() {
+                output.summary = Some(summary);
+            } else {
+                // forward anything the solutions printed on their own.
+                println!("{line}");
+            }
         }
 
         thread.join().unwrap();
-        cmd.wait()?;
+        let status = cmd.wait()?;
+
+        // NOTE: a part stopped by a limit exits the process on purpose, after reporting its record.
+        let is_limit = status.code() == Some(limits::EXIT_CODE)
+            && output
+                .records
+                .last()
+                .is_some_and(|record| record.failure.as_ref().is_some_and(Failure::is_limit));
+        if !status.success() && !is_limit {
+            return Err(Error::Status(status));
+        }
 
-        Ok(output)
+        Ok(())
+    }
+
+    fn is_same_step(a: &PartRecord, b: &PartRecord) -> bool {
+        a.day == b.day && a.input == b.input && a.part == b.part && a.variant == b.variant
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
+    pub fn print_record(year: Year, record: &PartRecord) {
+        if let Some(failure) = &record.failure {
+            print_failure(&part_label(record.part), failure, record.stats.mean);
+            return;
+        }
+
+        let duration_str = format_duration(&record.stats.mean, record.stats.samples);
+
+        if record.part == PARSE_PART {
+            println!("{}:{duration_str}", part_label(record.part));
+        } else {
+            let status_str = format_status(
+                year,
+                record.day,
+                record.input.as_deref(),
+                record.part,
+                record.answer.as_deref(),
+            );
+            print_result(
+                &record.answer,
+                &part_label(record.part),
+                &format!("{duration_str}{status_str}"),
+            );
+        }
+
+        print_stats(&record.stats);
+        print_alloc(record.alloc.as_ref());
+        print_counters(record.counters.as_ref());
+    }
+
+    pub fn records_to_timing(records: &[PartRecord], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
+            parse: None,
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            commit: None,
+            toolchain: None,
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+        // NOTE: parts without an answer are considered unsolved and not timed, unless they failed.
+        // only the default input and the parts themselves are timed, so timings stay comparable across machines and
+        // teammates.
+        for record in records.iter().filter(|r| {
+            r.day == day
+                && r.input.is_none()
+                && r.variant.is_none()
+                && (r.answer.is_some() || r.part == PARSE_PART || r.failure.is_some())
+        }) {
+            let timing = PartTiming {
+                alloc: record.alloc,
+                counters: record.counters,
+                failure: record.failure.clone(),
+                ..PartTiming::from(record.stats)
+            };
+            if timing.failure.is_none() {
+                timings.total_nanos += timing.nanos;
+            }
 
-                timings.total_nanos += nanos;
-            });
+            match record.part {
+                PARSE_PART => timings.parse = Some(timing),
+                1 => timings.part_1 = Some(timing),
+                2 => timings.part_2 = Some(timing),
+                _ => {}
+            }
+        }
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
-    }
-
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use std::time::Duration;
+
+        use super::records_to_timing;
+
+        use crate::{
+            day,
+            template::{record::PartRecord, stats::Stats},
+        };
 
-        use crate::day;
+        fn record(part: u8, answer: Option<&str>, nanos: u64) -> PartRecord {
+            PartRecord {
+                day: day!(1),
+                input: None,
+                part,
+                variant: None,
+                answer: answer.map(Into::into),
+                stats: Stats::single(Duration::from_nanos(nanos)),
+                alloc: None,
+                counters: None,
+                failure: None,
+            }
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
+        fn collects_execution_times() {
+            let res = records_to_timing(
+                &[record(1, Some("0"), 74), record(2, Some("10"), 74_130_000)],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.total_nanos, 74_130_074_f64);
+            let part_1 = res.part_1.unwrap();
+            assert_eq!(part_1.nanos, 74_f64);
+            assert_eq!(part_1.samples, Some(1));
+            assert_eq!(part_1.stats.unwrap().median, Duration::from_nanos(74));
+            assert_eq!(res.part_2.unwrap().nanos, 74_130_000_f64);
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
+        fn collects_answers_with_patterns() {
+            let res = records_to_timing(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    record(1, Some("@ @ @ ( ) ms (2s @ 5 samples)"), 2_000_000_000),
+                    record(2, Some("10s"), 100_000_000),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.total_nanos, 2_100_000_000_f64);
+            assert_eq!(res.part_1.unwrap().duration(), Duration::from_secs(2));
+            assert_eq!(res.part_2.unwrap().duration(), Duration::from_millis(100));
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
+        fn collects_missing_parts() {
+            let res = records_to_timing(&[record(1, None, 5), record(2, Some("1"), 5)], day!(1));
+            assert_eq!(res.total_nanos, 5_f64);
+            assert_eq!(res.part_1.is_none(), true);
+            assert_eq!(res.part_2.unwrap().nanos, 5_f64);
+        }
+
+        #[test]
+        fn collects_parse_step() {
+            let res = records_to_timing(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    record(0, None, 10),
+                    record(1, Some("1"), 5),
+                    record(2, None, 5),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
+            assert_eq!(res.total_nanos, 15_f64);
+            assert_eq!(res.parse.unwrap().nanos, 10_f64);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn ignores_records_of_other_days() {
+            let res = records_to_timing(&[record(1, Some("1"), 5)], day!(2));
+            assert_eq!(res.total_nanos, 0_f64);
+            assert_eq!(res.part_1.is_none(), true);
+        }
     }
 }
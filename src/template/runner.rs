@@ -2,71 +2,401 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::template::alloc::{self, AllocStats};
+use crate::template::answers::Answers;
+use crate::template::aoc_client::{AocClient, AocClientError, SubmissionResponse};
+use crate::template::limits::{self, Watched};
+use crate::template::panics;
+use crate::template::params;
+use crate::template::perf::{self, Counters};
+use crate::template::record::{part_label, Failure, OutputFormat, PartRecord, PARSE_PART};
+use crate::template::solution::{Answer, IntoAnswer, Solution, Variant};
+use crate::template::stats::Stats;
+use crate::template::submissions::{Submission, SubmissionCheck, Submissions};
+use crate::template::variants::{self, Selection};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{Day, Year, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+/// Parses the input once and runs every implemented part of `S` on it.
+/// `input_name` is the name of the input, `None` for the default input, see [`inputs`](super::inputs).
+/// For solutions with a parse step, the parse time and the combined time of all steps are reported as well.
+/// Panicking parts are reported as failed without affecting the other parts, a panicking parse step skips the parts.
+/// With `--variant <name>` or `--variants`, variants of the parts run instead of or next to them, see [`variants`].
+pub fn run_solution<S: Solution>(input: &str, input_name: Option<&str>) {
+    let selection = Selection::from_args();
+    let steps = match variants::steps(S::DAY, &S::METADATA, &selection) {
+        Ok(steps) => steps,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    // NOTE: a run that resumes after a part stopped by a limit only runs the steps after it.
+    let steps = match limits::ResumeAfter::from_args() {
+        Some(resume) => resume.remaining_steps(S::DAY, input_name, &steps),
+        None => steps,
+    };
+    if steps.is_empty() {
+        return;
+    }
+
+    if let Some(name) = input_name {
+        if OutputFormat::from_args() == OutputFormat::Human {
+            print_input_header(name);
+        }
+    }
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    if !S::METADATA.has_parse {
+        let input = S::parse(input);
+        for &(part, variant) in &steps {
+            run_part_of::<S>(&input, input_name, part, variant);
+        }
+        return;
+    }
+
+    let Some((input, parse_stats)) = run_parse::<S>(input, input_name) else {
+        return;
+    };
+    let mut combined = Some(parse_stats.mean);
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    for &(part, variant) in &steps {
+        let stats = run_part_of::<S>(&input, input_name, part, variant);
+        // NOTE: the combined time of a day with a failed part would be misleading.
+        combined = combined
+            .zip(stats)
+            .map(|(combined, stats)| combined + stats.mean);
+    }
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    // NOTE: variants run next to their parts with `--variants`, so only combine the times of the parts.
+    if let (Some(combined), OutputFormat::Human, Selection::Parts) =
+        (combined, OutputFormat::from_args(), selection)
+    {
+        print_combined(&combined);
+    }
+}
+
+fn run_part_of<S: Solution>(
+    input: &S::Input<'_>,
+    input_name: Option<&str>,
+    part: u8,
+    variant: Option<&'static str>,
+) -> Option<Stats> {
+    match (part, variant) {
+        (_, Some(name)) => run_variant(
+            |input| S::variant(name, part, input),
+            input,
+            S::YEAR,
+            S::DAY,
+            input_name,
+            Variant { name, part },
+        ),
+        (1, None) => run_part(
+            |input| S::part_one(input),
+            input,
+            S::YEAR,
+            S::DAY,
+            input_name,
+            1,
+        ),
+        (_, None) => run_part(
+            |input| S::part_two(input),
+            input,
+            S::YEAR,
+            S::DAY,
+            input_name,
+            2,
+        ),
+    }
+}
+
+fn run_parse<'a, S: Solution>(
+    input: &'a str,
+    input_name: Option<&str>,
+) -> Option<(S::Input<'a>, Stats)> {
+    let format = OutputFormat::from_args();
+    let label = part_label(PARSE_PART);
+
+    let watched = Watched {
+        year: S::YEAR,
+        day: S::DAY,
+        input: input_name.map(Into::into),
+        part: PARSE_PART,
+        variant: None,
+    };
+
+    let (parsed, stats, alloc, counters) = run_timed(S::parse, input, watched, Ok, |_| {
+        if format == OutputFormat::Human {
+            print!("{label}:");
+        }
+    })?;
+
+    match format {
+        OutputFormat::Human => {
+            print!("\r");
+            println!("{label}:{}", format_duration(&stats.mean, stats.samples));
+            print_stats(&stats);
+            print_alloc(alloc.as_ref());
+            print_counters(counters.as_ref());
+        }
+        OutputFormat::Json => {
+            let record = PartRecord {
+                day: S::DAY,
+                input: input_name.map(Into::into),
+                part: PARSE_PART,
+                variant: None,
+                answer: None,
+                stats,
+                alloc,
+                counters,
+                failure: None,
+            };
+            println!("{}", record.to_json_line());
+        }
+    }
+
+    Some((parsed, stats))
+}
+
+/// Prints a header above the results of a named input.
+pub(crate) fn print_input_header(name: &str) {
+    println!("{ANSI_ITALIC}Input {name}{ANSI_RESET}");
+}
+
+/// Prints the time it took to parse the input and solve every part once.
+pub(crate) fn print_combined(combined: &Duration) {
+    println!("Combined: {ANSI_ITALIC}{combined:.1?}{ANSI_RESET}");
+}
+
+pub fn run_part<I: Clone, T: IntoAnswer>(
+    func: impl Fn(I) -> T,
+    input: I,
+    year: Year,
+    day: Day,
+    input_name: Option<&str>,
+    part: u8,
+) -> Option<Stats> {
+    let watched = Watched {
+        year,
+        day,
+        input: input_name.map(Into::into),
+        part,
+        variant: None,
+    };
+
+    run_answer(func, input, watched, IntoAnswer::into_answer)
+}
+
+/// Runs a variant of a part like [`run_part`]. The answer is formatted by the variant, so its time includes that.
+fn run_variant<I: Clone>(
+    func: impl Fn(I) -> Answer,
+    input: I,
+    year: Year,
+    day: Day,
+    input_name: Option<&str>,
+    variant: Variant,
+) -> Option<Stats> {
+    let watched = Watched {
+        year,
+        day,
+        input: input_name.map(Into::into),
+        part: variant.part,
+        variant: Some(variant.name.into()),
+    };
+
+    run_answer(func, input, watched, |answer| answer)
+}
+
+/// Runs a part or variant and reports its answer, `into_answer` turns its result into an [`Answer`].
+fn run_answer<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    watched: Watched,
+    into_answer: impl FnOnce(T) -> Answer,
+) -> Option<Stats> {
+    let format = OutputFormat::from_args();
+    let part_str = step_label(watched.part, watched.variant.as_deref());
+    let Watched {
+        year, day, part, ..
+    } = watched;
+    let input_name = watched.input.clone();
+    let variant = watched.variant.clone();
+
+    let check = |result: T| into_answer(result).map_err(Failure::Errored);
+
+    let (result, stats, alloc, counters) = run_timed(func, input, watched, check, |result| {
+        if format == OutputFormat::Human {
+            print_result(result, &part_str, "");
+        }
+    })?;
+
+    match format {
+        OutputFormat::Human => {
+            let duration_str = format_duration(&stats.mean, stats.samples);
+            // NOTE: with overridden parameters, answers don't match the recorded answers of the input.
+            let status_str = if params::is_overridden() {
+                String::new()
+            } else {
+                format_status(year, day, input_name.as_deref(), part, result.as_deref())
+            };
+            print_result(&result, &part_str, &format!("{duration_str}{status_str}"));
+            print_stats(&stats);
+            print_alloc(alloc.as_ref());
+            print_counters(counters.as_ref());
+        }
+        OutputFormat::Json => {
+            let record = PartRecord {
+                day,
+                input: input_name.clone(),
+                part,
+                variant,
+                answer: result.clone(),
+                stats,
+                alloc,
+                counters,
+                failure: None,
+            };
+            println!("{}", record.to_json_line());
+        }
+    }
+
+    // NOTE: only the default input is the user's own, answers of other inputs can't be submitted.
+    if let (Some(result), None) = (result, input_name) {
+        submit_result(result, year, day, part);
+    }
+
+    Some(stats)
+}
+
+/// Label of a part or one of its variants, e.g. `Part 2 (crt)`.
+pub(crate) fn step_label(part: u8, variant: Option<&str>) -> String {
+    match variant {
+        Some(name) => format!("{} ({name})", part_label(part)),
+        None => part_label(part),
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
+///
+/// With `--alloc`, the function is run once more to count its allocations, see [`alloc::measure`].
+/// With `--counters`, hardware counters are collected while benching, see [`perf`].
+/// The first execution is subject to the `--timeout` and `--max-rss` limits, see [`limits`], and its result is passed
+/// through `check`. If it panics or fails the check, the failure is reported and `None` is returned, see [`panics`].
+fn run_timed<I: Clone, T, U>(
     func: impl Fn(I) -> T,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    watched: Watched,
+    check: impl FnOnce(T) -> Result<U, Failure>,
+    hook: impl Fn(&U),
+) -> Option<(U, Stats, Option<AllocStats>, Option<Counters>)> {
     let timer = Instant::now();
-    let result = {
-        let input = input.clone();
+    let result = limits::watch(watched.clone(), || {
+        panics::catch(|| {
+            let input = input.clone();
 
-        #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
+            #[cfg(feature = "dhat-heap")]
+            let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
-    };
+            func(input)
+        })
+    });
     let base_time = timer.elapsed();
 
+    let result = match result.map_err(Failure::Panicked).and_then(check) {
+        Ok(result) => result,
+        Err(failure) => {
+            report_failure(&watched, &failure, base_time);
+            return None;
+        }
+    };
+
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let alloc_input = input.clone();
+    let alloc = alloc::measure(|| func(alloc_input));
+
+    let (stats, counters) = if std::env::args().any(|x| x == "--time") {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        (Stats::single(base_time), None)
     };
 
-    (result, run.0, run.1)
+    Some((result, stats, alloc, counters))
+}
+
+/// Reports a part that failed after `elapsed`, as a human readable line or a JSON record.
+pub(crate) fn report_failure(watched: &Watched, failure: &Failure, elapsed: Duration) {
+    match OutputFormat::from_args() {
+        OutputFormat::Human => {
+            print!("\r");
+            print_failure(
+                &step_label(watched.part, watched.variant.as_deref()),
+                failure,
+                elapsed,
+            );
+        }
+        OutputFormat::Json => {
+            let record = PartRecord {
+                day: watched.day,
+                input: watched.input.clone(),
+                part: watched.part,
+                variant: watched.variant.clone(),
+                answer: None,
+                stats: Stats::single(elapsed),
+                alloc: None,
+                counters: None,
+                failure: Some(failure.clone()),
+            };
+            println!("{}", record.to_json_line());
+        }
+    }
+    let _ = stdout().flush();
+}
+
+/// Prints a failed part, with the panic message or error chain below it.
+pub(crate) fn print_failure(part: &str, failure: &Failure, elapsed: Duration) {
+    println!("{part}: ✖ {failure} ({elapsed:.1?})");
+    if let Some(message) = failure.message() {
+        println!("  {ANSI_ITALIC}{message}{ANSI_RESET}");
+    }
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+) -> (Stats, Option<Counters>) {
     let mut stdout = stdout();
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    if OutputFormat::from_args() == OutputFormat::Human {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout.flush();
+    }
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let bench_iterations =
+        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
-    let mut timers: Vec<Duration> = vec![];
+    // warm up caches and branch predictors before taking samples, for up to a tenth of the budget.
+    let warmup_iterations = cmp::max(bench_iterations / 10, 1);
+    let warmup_timer = Instant::now();
+    for _ in 0..warmup_iterations {
+        black_box(func(black_box(input.clone())));
+        if warmup_timer.elapsed() > Duration::from_millis(100) {
+            break;
+        }
+    }
+
+    let mut timers: Vec<Duration> = Vec::with_capacity(bench_iterations as usize);
+    let counter_group = perf::open_if_enabled();
+
+    if let Some(group) = &counter_group {
+        group.enable();
+    }
 
     for _ in 0..bench_iterations {
         // need a clone here to make the borrow checker happy.
@@ -76,22 +406,16 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
-}
+    // NOTE: counters include the input clones, which are cheap compared to the solution for all inputs we pass.
+    let counters = counter_group.map(|group| {
+        group.disable();
+        group.read(bench_iterations)
+    });
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+    (Stats::from_samples(&mut timers), counters)
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
+pub(crate) fn format_duration(duration: &Duration, samples: u128) -> String {
     if samples == 1 {
         format!(" ({duration:.1?})")
     } else {
@@ -99,7 +423,45 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+/// Checks an answer against the recorded answers of its day and input, see [`Answers`].
+pub(crate) fn format_status(
+    year: Year,
+    day: Day,
+    input_name: Option<&str>,
+    part: u8,
+    answer: Option<&str>,
+) -> String {
+    match Answers::read(year, day, input_name) {
+        Ok(answers) => format!(" · {}", answers.check(part, answer)),
+        Err(e) => {
+            eprintln!("{e}");
+            String::new()
+        }
+    }
+}
+
+/// Prints the heap usage below a result, if it was measured.
+pub(crate) fn print_alloc(alloc: Option<&AllocStats>) {
+    if let Some(alloc) = alloc {
+        println!("  {ANSI_ITALIC}{alloc}{ANSI_RESET}");
+    }
+}
+
+/// Prints the hardware counters below a result, if they were collected.
+pub(crate) fn print_counters(counters: Option<&Counters>) {
+    if let Some(counters) = counters {
+        println!("  {ANSI_ITALIC}{counters}{ANSI_RESET}");
+    }
+}
+
+/// Prints the sample distribution below a benched result.
+pub(crate) fn print_stats(stats: &Stats) {
+    if stats.samples > 1 {
+        println!("  {ANSI_ITALIC}{stats}{ANSI_RESET}");
+    }
+}
+
+pub(crate) fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
@@ -136,12 +498,13 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. a session cookie is configured, see [`AocClient::from_env`].
 fn submit_result<T: Display>(
     result: T,
+    year: Year,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<SubmissionResponse, AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -164,11 +527,52 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    if params::is_overridden() {
+        eprintln!("Not submitting: parameters were overridden with `--param`.");
+        return None;
+    }
+
+    let client = match AocClient::from_env(year) {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("failed to set up client: {e}");
+            process::exit(1);
+        }
+    };
+
+    let answer = result.to_string();
+    let mut submissions = match Submissions::read_from_file(year) {
+        Ok(submissions) => submissions,
+        Err(e) => {
+            eprintln!("Not submitting: {e}");
+            return None;
+        }
+    };
+
+    match submissions.check(day, part, &answer) {
+        SubmissionCheck::Ok => {}
+        SubmissionCheck::Warn(reason) => println!("Warning: {reason}"),
+        SubmissionCheck::Refuse(reason) => {
+            eprintln!("Not submitting: {reason}");
+            return None;
+        }
+    }
+
+    println!("Submitting result...");
+    let response = client.submit(day, part, &answer);
+
+    match &response {
+        Ok(response) => {
+            println!("{}", response.message);
+            submissions
+                .data
+                .push(Submission::now(day, part, &answer, response.outcome));
+            if let Err(e) = submissions.store_file(year) {
+                eprintln!("failed to store submission: {e}");
+            }
+        }
+        Err(e) => eprintln!("failed to submit result: {e}"),
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(response)
 }
@@ -0,0 +1,127 @@
+/// Benches solutions at increasing thread counts to show which parallelizations pay off.
+use std::{collections::HashMap, time::Duration};
+
+use crate::template::{
+    all_days,
+    run_multi::{child_commands, RunOptions},
+    threads, Day, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+
+/// Timing of one day at one thread count, relative to its single threaded timing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScalingStep {
+    pub threads: usize,
+    pub duration: Duration,
+    /// Single threaded duration divided by this duration.
+    pub speedup: f64,
+    /// Speedup divided by the number of threads, `1.0` for perfect scaling.
+    pub efficiency: f64,
+}
+
+/// Derives speedup and efficiency from the total duration of a day at every thread count, ordered by thread count.
+/// The first entry is the baseline the others are compared with.
+pub fn scaling_steps(durations: &[(usize, Duration)]) -> Vec<ScalingStep> {
+    let Some((_, baseline)) = durations.first() else {
+        return vec![];
+    };
+
+    durations
+        .iter()
+        .map(|(threads, duration)| {
+            let speedup = baseline.as_secs_f64() / duration.as_secs_f64().max(f64::EPSILON);
+            #[allow(clippy::cast_precision_loss)]
+            ScalingStep {
+                threads: *threads,
+                duration: *duration,
+                speedup,
+                efficiency: speedup / *threads as f64,
+            }
+        })
+        .collect()
+}
+
+/// Benches `days` of `year` once for every thread count of [`threads::ladder`] up to `max_threads` and prints
+/// a report per day. `max_threads` defaults to the number of threads the machine can run in parallel.
+pub fn run_scaling(year: Year, days: &[Day], max_threads: Option<usize>, is_counters: bool) {
+    let days: Vec<Day> = all_days().filter(|day| days.contains(day)).collect();
+    let ladder = threads::ladder(max_threads.unwrap_or_else(threads::available));
+
+    let mut durations: HashMap<Day, Vec<(usize, Duration)>> = HashMap::new();
+
+    for &threads in &ladder {
+        println!("{ANSI_ITALIC}Benching with {threads} thread(s)...{ANSI_RESET}");
+
+        let options = RunOptions {
+            is_release: true,
+            is_timed: true,
+            is_counters,
+            threads: Some(threads),
+            ..RunOptions::default()
+        };
+
+        let output = match child_commands::run_solutions(year, &days, &options, |_| {}) {
+            Ok(output) => output,
+            Err(e) => {
+                eprintln!("Failed to run solutions: {e}");
+                return;
+            }
+        };
+
+        for &day in &days {
+            if output.records.iter().any(|r| r.day == day) {
+                let timing = child_commands::records_to_timing(&output.records, day);
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let duration = Duration::from_nanos(timing.total_nanos.round() as u64);
+                durations.entry(day).or_default().push((threads, duration));
+            }
+        }
+    }
+
+    for day in days {
+        println!();
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        match durations.get(&day) {
+            Some(durations) => print_steps(&scaling_steps(durations)),
+            None => println!("Not solved."),
+        }
+    }
+}
+
+fn print_steps(steps: &[ScalingStep]) {
+    println!("Threads  Time          Speedup  Efficiency");
+    for step in steps {
+        let duration = format!("{:.1?}", step.duration);
+        println!(
+            "{:<8} {duration:<13} {:<8} {ANSI_ITALIC}{:.0}%{ANSI_RESET}",
+            step.threads,
+            format!("{:.2}×", step.speedup),
+            step.efficiency * 100.0
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::scaling_steps;
+
+    #[test]
+    fn computes_speedup_and_efficiency() {
+        let steps = scaling_steps(&[
+            (1, Duration::from_millis(8)),
+            (2, Duration::from_millis(4)),
+            (4, Duration::from_millis(4)),
+        ]);
+        assert_eq!(steps.len(), 3);
+        assert_eq!(steps[0].speedup, 1.0);
+        assert_eq!(steps[1].speedup, 2.0);
+        assert_eq!(steps[1].efficiency, 1.0);
+        assert_eq!(steps[2].efficiency, 0.5);
+        assert!(scaling_steps(&[]).is_empty());
+    }
+}
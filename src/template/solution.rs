@@ -0,0 +1,358 @@
+/// Common interface of all solutions.
+use std::{convert::Infallible, error::Error, fmt::Display};
+
+use crate::template::{runner, Day, Year};
+
+/// A solution for a single day of advent.
+///
+/// This is usually implemented by the [`solution!`](crate::solution) macro, which wraps the free
+/// `parse` / `part_one` / `part_two` functions of a day in a unit struct called `Puzzle`.
+pub trait Solution {
+    /// The year this solution belongs to.
+    const YEAR: Year;
+
+    /// The day this solution belongs to.
+    const DAY: Day;
+
+    /// Static information about the solution.
+    const METADATA: Metadata;
+
+    /// Representation of the puzzle input that is passed to both parts.
+    type Input<'a>;
+
+    /// Turns the raw puzzle input into [`Solution::Input`].
+    fn parse(input: &str) -> Self::Input<'_>;
+
+    fn part_one(input: &Self::Input<'_>) -> impl IntoAnswer;
+
+    fn part_two(input: &Self::Input<'_>) -> impl IntoAnswer;
+
+    /// Runs the variant `name` of `part`, see [`Metadata::variants`].
+    fn variant(name: &str, part: u8, input: &Self::Input<'_>) -> Answer {
+        let _ = input;
+        Err(format!("unknown variant `{name}` of part {part}"))
+    }
+}
+
+/// Outcome of a part: its answer, `None` if it is not implemented, or the chain of errors it failed with.
+pub type Answer = Result<Option<String>, String>;
+
+/// Return types of part functions: `Option<T>`, or `Result<T, E>` for parts that can fail with a diagnosable error.
+/// Errors can be anything that converts into a `Box<dyn Error>`, including error types, `String` and `&str`.
+pub trait IntoAnswer {
+    fn into_answer(self) -> Answer;
+}
+
+impl<T: Display> IntoAnswer for Option<T> {
+    fn into_answer(self) -> Answer {
+        Ok(self.map(|x| x.to_string()))
+    }
+}
+
+impl<T: Display, E: Into<Box<dyn Error>>> IntoAnswer for Result<T, E> {
+    fn into_answer(self) -> Answer {
+        match self {
+            Ok(answer) => Ok(Some(answer.to_string())),
+            Err(error) => {
+                let error: Box<dyn Error> = error.into();
+                if error.is::<NotImplemented>() {
+                    Ok(None)
+                } else {
+                    Err(error_chain(error.as_ref()))
+                }
+            }
+        }
+    }
+}
+
+/// Result of parts that fail with any error, as scaffolded by `cargo scaffold`.
+pub type PartResult<T> = Result<T, Box<dyn Error>>;
+
+/// Error of parts that are not implemented yet. They are reported like parts that return `None`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NotImplemented;
+
+impl Display for NotImplemented {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("not implemented")
+    }
+}
+
+impl Error for NotImplemented {}
+
+/// Joins the messages of an error and all of its sources, e.g. `invalid hand: invalid digit found in string`.
+pub fn error_chain(error: &dyn Error) -> String {
+    let mut chain = error.to_string();
+    let mut source = error.source();
+
+    while let Some(error) = source {
+        chain.push_str(": ");
+        chain.push_str(&error.to_string());
+        source = error.source();
+    }
+
+    chain
+}
+
+/// Static information about a [`Solution`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Metadata {
+    /// The parts that are implemented, in order.
+    pub parts: &'static [u8],
+    /// Path of the source file the solution is defined in.
+    pub source: &'static str,
+    /// Whether the solution declares a parse step. Otherwise, [`Solution::parse`] just passes the input through.
+    pub has_parse: bool,
+    /// Alternative implementations of the parts, run with [`Solution::variant`].
+    pub variants: &'static [Variant],
+}
+
+/// A named alternative implementation of a part, e.g. a brute force solution to check a faster one against.
+/// Both parts can have a variant of the same name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Variant {
+    pub name: &'static str,
+    pub part: u8,
+}
+
+impl Metadata {
+    /// The variants called `name`, for every part that has one.
+    pub fn variants_named(&self, name: &str) -> Vec<Variant> {
+        self.variants
+            .iter()
+            .filter(|variant| variant.name == name)
+            .copied()
+            .collect()
+    }
+}
+
+/// Stand-in for parts that are not implemented, see the second parameter of [`solution!`](crate::solution).
+pub fn unsolved<I>(_input: I) -> Option<Infallible> {
+    None
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Type-erased handle to a [`Solution`], so solutions can be listed and called generically.
+#[derive(Clone, Copy, Debug)]
+pub struct Registration {
+    pub year: Year,
+    pub day: Day,
+    pub metadata: Metadata,
+    /// Runs all parts on an input through the runner, printing results. The second parameter names the input.
+    pub run: fn(&str, Option<&str>),
+    /// Solves all parts once without any output. Unimplemented parts are `Ok(None)`.
+    pub solve: fn(&str) -> [Answer; 2],
+    /// Solves a single part once without any output.
+    pub solve_part: fn(&str, u8) -> Answer,
+    /// Solves a variant once without any output, see [`Metadata::variants`].
+    pub solve_variant: fn(&str, Variant) -> Answer,
+}
+
+impl Registration {
+    pub const fn of<S: Solution>() -> Self {
+        Self {
+            year: S::YEAR,
+            day: S::DAY,
+            metadata: S::METADATA,
+            run: runner::run_solution::<S>,
+            solve: solve::<S>,
+            solve_part: solve_part::<S>,
+            solve_variant: solve_variant::<S>,
+        }
+    }
+}
+
+/// Solves every implemented part of `S` once and returns the answers.
+pub fn solve<S: Solution>(input: &str) -> [Answer; 2] {
+    let input = S::parse(input);
+    let mut answers = [Ok(None), Ok(None)];
+
+    for &part in S::METADATA.parts {
+        answers[usize::from(part) - 1] = match part {
+            1 => S::part_one(&input).into_answer(),
+            _ => S::part_two(&input).into_answer(),
+        };
+    }
+
+    answers
+}
+
+/// Solves `part` of `S` once and returns the answer. Unimplemented parts are `Ok(None)`.
+pub fn solve_part<S: Solution>(input: &str, part: u8) -> Answer {
+    if !S::METADATA.parts.contains(&part) {
+        return Ok(None);
+    }
+
+    let input = S::parse(input);
+    match part {
+        1 => S::part_one(&input).into_answer(),
+        _ => S::part_two(&input).into_answer(),
+    }
+}
+
+/// Solves `variant` of `S` once and returns the answer.
+pub fn solve_variant<S: Solution>(input: &str, variant: Variant) -> Answer {
+    let input = S::parse(input);
+    S::variant(variant.name, variant.part, &input)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{error::Error, fmt::Display, num::ParseIntError};
+
+    use super::{
+        Answer, IntoAnswer, Metadata, NotImplemented, PartResult, Registration, Solution, Variant,
+    };
+    use crate::{
+        day,
+        template::{Day, Year},
+        year,
+    };
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const YEAR: Year = Year::PRIMARY;
+        const DAY: Day = day!(1);
+        const METADATA: Metadata = Metadata {
+            parts: &[1, 2],
+            source: file!(),
+            has_parse: true,
+            variants: &[Variant {
+                name: "sorted",
+                part: 2,
+            }],
+        };
+
+        type Input<'a> = Vec<u32>;
+
+        fn parse(input: &str) -> Vec<u32> {
+            input.lines().map(|x| x.parse().unwrap()).collect()
+        }
+
+        fn part_one(input: &Self::Input<'_>) -> impl IntoAnswer {
+            Some(input.iter().sum::<u32>())
+        }
+
+        fn part_two(input: &Self::Input<'_>) -> impl IntoAnswer {
+            input.iter().max().copied()
+        }
+
+        fn variant(name: &str, part: u8, input: &Self::Input<'_>) -> Answer {
+            match (name, part) {
+                ("sorted", 2) => {
+                    let mut sorted = input.clone();
+                    sorted.sort_unstable();
+                    sorted.last().into_answer()
+                }
+                _ => Err(format!("unknown variant `{name}` of part {part}")),
+            }
+        }
+    }
+
+    struct FirstOnly;
+
+    impl Solution for FirstOnly {
+        const YEAR: Year = year!(2015);
+        const DAY: Day = day!(2);
+        const METADATA: Metadata = Metadata {
+            parts: &[1],
+            source: file!(),
+            has_parse: false,
+            variants: &[],
+        };
+
+        type Input<'a> = &'a str;
+
+        fn parse(input: &str) -> &str {
+            input
+        }
+
+        fn part_one(input: &Self::Input<'_>) -> impl IntoAnswer {
+            Some(input.len())
+        }
+
+        fn part_two(input: &Self::Input<'_>) -> impl IntoAnswer {
+            super::unsolved(input)
+        }
+    }
+
+    #[test]
+    fn solves_parsed_input() {
+        let registration = Registration::of::<Sum>();
+        assert_eq!(registration.day, day!(1));
+        assert_eq!(
+            (registration.solve)("3\n7\n2"),
+            [Ok(Some("12".to_string())), Ok(Some("7".to_string()))]
+        );
+    }
+
+    #[test]
+    fn skips_unimplemented_parts() {
+        let registration = Registration::of::<FirstOnly>();
+        assert_eq!(registration.year, year!(2015));
+        assert_eq!(registration.metadata.parts, &[1]);
+        assert_eq!(
+            (registration.solve)("abc"),
+            [Ok(Some("3".to_string())), Ok(None)]
+        );
+        assert_eq!(
+            (registration.solve_part)("abc", 1),
+            Ok(Some("3".to_string()))
+        );
+        assert_eq!((registration.solve_part)("abc", 2), Ok(None));
+    }
+
+    #[test]
+    fn solves_variants() {
+        let registration = Registration::of::<Sum>();
+        let sorted = Variant {
+            name: "sorted",
+            part: 2,
+        };
+        assert_eq!(registration.metadata.variants_named("sorted"), [sorted]);
+        assert!(registration.metadata.variants_named("crt").is_empty());
+        assert_eq!(
+            (registration.solve_variant)("3\n7\n2", sorted),
+            Ok(Some("7".to_string()))
+        );
+
+        let registration = Registration::of::<FirstOnly>();
+        assert!((registration.solve_variant)("abc", sorted).is_err());
+    }
+
+    #[derive(Debug)]
+    struct ParseHandError(ParseIntError);
+
+    impl Display for ParseHandError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("invalid hand")
+        }
+    }
+
+    impl Error for ParseHandError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn reports_error_chains() {
+        let parse = |s: &str| s.parse::<u32>().map_err(ParseHandError);
+        assert_eq!(parse("7").into_answer(), Ok(Some("7".to_string())));
+        assert_eq!(
+            parse("x").into_answer(),
+            Err("invalid hand: invalid digit found in string".to_string())
+        );
+        assert_eq!(
+            Err::<u32, _>("no loop found").into_answer(),
+            Err("no loop found".to_string())
+        );
+
+        let unimplemented: PartResult<u32> = Err(NotImplemented.into());
+        assert_eq!(unimplemented.into_answer(), Ok(None));
+    }
+}
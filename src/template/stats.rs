@@ -0,0 +1,233 @@
+/// Summary statistics over benchmark samples.
+use std::{collections::HashMap, fmt::Display, time::Duration};
+use tinyjson::JsonValue;
+
+/// Distribution of the durations measured while benching a solution part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub samples: u128,
+    pub mean: Duration,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub p99: Duration,
+    pub std_dev: Duration,
+    /// Number of samples outside of the Tukey fences (1.5 × IQR beyond the quartiles).
+    pub outliers: u128,
+}
+
+impl Stats {
+    /// Statistics for a part that was executed exactly once.
+    pub fn single(duration: Duration) -> Self {
+        Self {
+            samples: 1,
+            mean: duration,
+            min: duration,
+            median: duration,
+            p95: duration,
+            p99: duration,
+            std_dev: Duration::ZERO,
+            outliers: 0,
+        }
+    }
+
+    /// Computes statistics from a non-empty list of samples. Sorts `samples` in place.
+    pub fn from_samples(samples: &mut [Duration]) -> Self {
+        assert!(!samples.is_empty(), "expected at least one sample");
+        samples.sort_unstable();
+
+        let n = samples.len() as u128;
+        let mean_nanos = samples.iter().map(Duration::as_nanos).sum::<u128>() / n;
+
+        #[allow(clippy::cast_precision_loss)]
+        let variance = samples
+            .iter()
+            .map(|x| {
+                let diff = x.as_nanos() as f64 - mean_nanos as f64;
+                diff * diff
+            })
+            .sum::<f64>()
+            / samples.len() as f64;
+
+        let q1 = percentile(samples, 25.0).as_nanos();
+        let q3 = percentile(samples, 75.0).as_nanos();
+        let fence = (q3 - q1) * 3 / 2;
+        let lower_fence = q1.saturating_sub(fence);
+        let upper_fence = q3 + fence;
+
+        let outliers = samples
+            .iter()
+            .map(Duration::as_nanos)
+            .filter(|&x| x < lower_fence || x > upper_fence)
+            .count() as u128;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Self {
+            samples: n,
+            mean: Duration::from_nanos(mean_nanos as u64),
+            min: samples[0],
+            median: percentile(samples, 50.0),
+            p95: percentile(samples, 95.0),
+            p99: percentile(samples, 99.0),
+            std_dev: Duration::from_nanos(variance.sqrt().round() as u64),
+            outliers,
+        }
+    }
+}
+
+/// Nearest-rank percentile of a sorted, non-empty slice.
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {:.1?} · median {:.1?} · p95 {:.1?} · p99 {:.1?} · σ {:.1?} · {} outliers",
+            self.min, self.median, self.p95, self.p99, self.std_dev, self.outliers
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+const DURATION_KEYS: [&str; 6] = [
+    "duration_nanos",
+    "min_nanos",
+    "median_nanos",
+    "p95_nanos",
+    "p99_nanos",
+    "std_dev_nanos",
+];
+
+impl Stats {
+    /// Writes the statistics as flat keys into a JSON object.
+    pub(crate) fn insert_into(&self, map: &mut HashMap<String, JsonValue>) {
+        let durations = [
+            self.mean,
+            self.min,
+            self.median,
+            self.p95,
+            self.p99,
+            self.std_dev,
+        ];
+
+        #[allow(clippy::cast_precision_loss)]
+        for (key, duration) in DURATION_KEYS.iter().zip(durations) {
+            map.insert((*key).into(), JsonValue::Number(duration.as_nanos() as f64));
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert("samples".into(), JsonValue::Number(self.samples as f64));
+            map.insert("outliers".into(), JsonValue::Number(self.outliers as f64));
+        }
+    }
+
+    /// Reads statistics written by [`Stats::insert_into`] from a JSON object.
+    pub(crate) fn read_from(map: &HashMap<String, JsonValue>) -> Result<Self, String> {
+        let number = |key: &str| {
+            map.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected `{key}` to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let duration = |key: &str| number(key).map(|x| Duration::from_nanos(x as u64));
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(Self {
+            samples: number("samples")? as u128,
+            mean: duration(DURATION_KEYS[0])?,
+            min: duration(DURATION_KEYS[1])?,
+            median: duration(DURATION_KEYS[2])?,
+            p95: duration(DURATION_KEYS[3])?,
+            p99: duration(DURATION_KEYS[4])?,
+            std_dev: duration(DURATION_KEYS[5])?,
+            outliers: number("outliers")? as u128,
+        })
+    }
+}
+
+impl From<&Stats> for JsonValue {
+    fn from(value: &Stats) -> Self {
+        let mut map = HashMap::new();
+        value.insert_into(&mut map);
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+        Stats::read_from(json)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::Stats;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn computes_order_statistics() {
+        let mut samples = nanos(&(1..=100).rev().collect::<Vec<_>>());
+        let stats = Stats::from_samples(&mut samples);
+        assert_eq!(stats.samples, 100);
+        assert_eq!(stats.min, Duration::from_nanos(1));
+        assert_eq!(stats.median, Duration::from_nanos(50));
+        assert_eq!(stats.p95, Duration::from_nanos(95));
+        assert_eq!(stats.p99, Duration::from_nanos(99));
+        assert_eq!(stats.mean, Duration::from_nanos(50));
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn computes_standard_deviation() {
+        let mut samples = nanos(&[2, 4, 4, 4, 5, 5, 7, 9]);
+        let stats = Stats::from_samples(&mut samples);
+        assert_eq!(stats.mean, Duration::from_nanos(5));
+        assert_eq!(stats.std_dev, Duration::from_nanos(2));
+    }
+
+    #[test]
+    fn counts_outliers() {
+        let mut samples = nanos(&[10, 11, 10, 12, 11, 10, 11, 500, 1]);
+        let stats = Stats::from_samples(&mut samples);
+        assert_eq!(stats.outliers, 2);
+        assert_eq!(stats.median, Duration::from_nanos(11));
+    }
+
+    #[test]
+    fn handles_single_samples() {
+        let mut samples = nanos(&[42]);
+        let stats = Stats::from_samples(&mut samples);
+        assert_eq!(stats, Stats::single(Duration::from_nanos(42)));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let mut samples = nanos(&[10, 11, 10, 12, 11, 10, 11, 500, 1]);
+        let stats = Stats::from_samples(&mut samples);
+        let json = tinyjson::JsonValue::from(&stats);
+        assert_eq!(Stats::try_from(&json).unwrap(), stats);
+    }
+}
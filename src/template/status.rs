@@ -0,0 +1,270 @@
+/// Status of every part of a multi-day run, so failed parts don't hide between the timings.
+use std::{collections::BTreeMap, fmt::Display};
+
+use crate::template::{
+    answers::{AnswerStatus, Answers},
+    record::{Failure, PartRecord, PARSE_PART},
+    Day, Year, ANSI_BOLD, ANSI_RESET,
+};
+
+/// Outcome of a single part, ordered from best to worst.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PartStatus {
+    /// The answer matches the recorded answer, see [`Answers`].
+    Correct,
+    /// The answer differs from the recorded answer.
+    WrongAnswer,
+    /// There is an answer, but no recorded answer to check it against.
+    Unverified,
+    /// The part is missing or returned `None`.
+    NotImplemented,
+    /// The parse step panicked, so the part did not run.
+    ParseError,
+    /// The part returned an error.
+    Errored,
+    Panicked,
+    TimedOut,
+    OutOfMemory,
+    /// The day has no solution or no input.
+    NotSolved,
+}
+
+impl Display for PartStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            PartStatus::Correct => "correct",
+            PartStatus::WrongAnswer => "wrong answer",
+            PartStatus::Unverified => "unverified",
+            PartStatus::NotImplemented => "not implemented",
+            PartStatus::ParseError => "parse error",
+            PartStatus::Errored => "error",
+            PartStatus::Panicked => "panicked",
+            PartStatus::TimedOut => "timed out",
+            PartStatus::OutOfMemory => "out of memory",
+            PartStatus::NotSolved => "not solved",
+        })
+    }
+}
+
+impl PartStatus {
+    /// Status of the part of `record`, where `answer` is the result of checking its answer.
+    pub fn of(record: &PartRecord, answer: &AnswerStatus) -> Self {
+        match (&record.failure, &record.answer) {
+            (Some(Failure::Panicked(_)), _) if record.part == PARSE_PART => PartStatus::ParseError,
+            (Some(Failure::Panicked(_)), _) => PartStatus::Panicked,
+            (Some(Failure::Errored(_)), _) => PartStatus::Errored,
+            (Some(Failure::TimedOut), _) => PartStatus::TimedOut,
+            (Some(Failure::OutOfMemory), _) => PartStatus::OutOfMemory,
+            (None, None) => PartStatus::NotImplemented,
+            (None, Some(_)) => match answer {
+                AnswerStatus::Correct => PartStatus::Correct,
+                AnswerStatus::Incorrect(_) => PartStatus::WrongAnswer,
+                AnswerStatus::Unknown => PartStatus::Unverified,
+            },
+        }
+    }
+}
+
+/// Status of both parts of a day on one of its inputs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StatusRow {
+    pub day: Day,
+    /// Name of the input, `None` for the default input.
+    pub input: Option<String>,
+    pub parts: [PartStatus; 2],
+}
+
+impl StatusRow {
+    fn label(&self) -> String {
+        match &self.input {
+            Some(name) => format!("Day {} ({name})", self.day),
+            None => format!("Day {}", self.day),
+        }
+    }
+}
+
+/// Collects the status of every part of `days` from their records, with a row per day and input.
+/// `check` checks the answer of a record against its recorded answer.
+pub fn status_rows(
+    days: &[Day],
+    records: &[PartRecord],
+    check: impl Fn(&PartRecord) -> AnswerStatus,
+) -> Vec<StatusRow> {
+    let mut rows: Vec<StatusRow> = vec![];
+
+    for &day in days {
+        let rows_before = rows.len();
+
+        for record in records
+            .iter()
+            .filter(|r| r.day == day && r.variant.is_none())
+        {
+            let index = match rows[rows_before..]
+                .iter()
+                .position(|row| row.input == record.input)
+            {
+                Some(i) => rows_before + i,
+                None => {
+                    rows.push(StatusRow {
+                        day,
+                        input: record.input.clone(),
+                        parts: [PartStatus::NotImplemented; 2],
+                    });
+                    rows.len() - 1
+                }
+            };
+
+            let status = PartStatus::of(record, &check(record));
+            let row = &mut rows[index];
+
+            if record.part != PARSE_PART {
+                row.parts[usize::from(record.part) - 1] = status;
+            } else if record.failure.is_some() {
+                // NOTE: the parts don't run if the parse step failed.
+                row.parts = [status; 2];
+            }
+        }
+
+        if rows.len() == rows_before {
+            rows.push(StatusRow {
+                day,
+                input: None,
+                parts: [PartStatus::NotSolved; 2],
+            });
+        }
+    }
+
+    rows
+}
+
+/// Prints the status of every part of `days` as a table, followed by the number of parts per status.
+pub fn print_status_table(year: Year, days: &[Day], records: &[PartRecord]) {
+    let rows = status_rows(days, records, |record| {
+        Answers::read(year, record.day, record.input.as_deref())
+            .map_or(AnswerStatus::Unknown, |answers| {
+                answers.check(record.part, record.answer.as_deref())
+            })
+    });
+
+    let width = rows.iter().map(|row| row.label().len()).max().unwrap_or(0) + 2;
+    // NOTE: padding is applied to strings, `Display` of `PartStatus` ignores it.
+    let status_width = "not implemented".len() + 2;
+
+    println!("\n{ANSI_BOLD}Status{ANSI_RESET}");
+    println!("------");
+    println!("{:width$}{:status_width$}Part 2", "", "Part 1");
+
+    let mut counts: BTreeMap<PartStatus, usize> = BTreeMap::new();
+
+    for row in &rows {
+        let [part_1, part_2] = row.parts;
+        println!(
+            "{:width$}{:status_width$}{part_2}",
+            row.label(),
+            part_1.to_string()
+        );
+        for status in row.parts {
+            *counts.entry(status).or_default() += 1;
+        }
+    }
+
+    let counts: Vec<String> = counts
+        .iter()
+        .map(|(status, count)| format!("{count} {status}"))
+        .collect();
+    println!("\n{}.", counts.join(", "));
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{status_rows, PartStatus, StatusRow};
+    use crate::{
+        day,
+        template::{
+            answers::AnswerStatus,
+            record::{Failure, PartRecord},
+            stats::Stats,
+            Day,
+        },
+    };
+
+    fn record(day: u8, part: u8, answer: Option<&str>, failure: Option<Failure>) -> PartRecord {
+        PartRecord {
+            day: Day::new(day).unwrap(),
+            input: None,
+            part,
+            variant: None,
+            answer: answer.map(Into::into),
+            stats: Stats::single(Duration::from_micros(1)),
+            alloc: None,
+            counters: None,
+            failure,
+        }
+    }
+
+    #[test]
+    fn distinguishes_statuses() {
+        let records = [
+            record(1, 1, Some("42"), None),
+            record(1, 2, Some("7"), None),
+            record(2, 1, Some("1"), None),
+            record(2, 2, None, None),
+            record(3, 0, None, Some(Failure::Panicked("valid input".into()))),
+            record(4, 1, None, Some(Failure::Panicked("overflow".into()))),
+            record(4, 2, None, Some(Failure::TimedOut)),
+            record(5, 1, None, Some(Failure::Errored("no loop found".into()))),
+        ];
+        let days = [day!(1), day!(2), day!(3), day!(4), day!(5), day!(6)];
+
+        let rows = status_rows(&days, &records, |record| match record.answer.as_deref() {
+            Some("42") => AnswerStatus::Correct,
+            Some("7") => AnswerStatus::Incorrect("8".into()),
+            _ => AnswerStatus::Unknown,
+        });
+
+        let parts: Vec<[PartStatus; 2]> = rows.iter().map(|row| row.parts).collect();
+        assert_eq!(
+            parts,
+            [
+                [PartStatus::Correct, PartStatus::WrongAnswer],
+                [PartStatus::Unverified, PartStatus::NotImplemented],
+                [PartStatus::ParseError, PartStatus::ParseError],
+                [PartStatus::Panicked, PartStatus::TimedOut],
+                [PartStatus::Errored, PartStatus::NotImplemented],
+                [PartStatus::NotSolved, PartStatus::NotSolved],
+            ]
+        );
+    }
+
+    #[test]
+    fn adds_rows_for_named_inputs() {
+        let records = [
+            record(6, 1, Some("1"), None),
+            PartRecord {
+                input: Some("alice".into()),
+                ..record(6, 1, None, Some(Failure::OutOfMemory))
+            },
+        ];
+
+        let rows = status_rows(&[day!(6)], &records, |_| AnswerStatus::Unknown);
+        assert_eq!(
+            rows,
+            [
+                StatusRow {
+                    day: day!(6),
+                    input: None,
+                    parts: [PartStatus::Unverified, PartStatus::NotImplemented],
+                },
+                StatusRow {
+                    day: day!(6),
+                    input: Some("alice".into()),
+                    parts: [PartStatus::OutOfMemory, PartStatus::NotImplemented],
+                },
+            ]
+        );
+    }
+}
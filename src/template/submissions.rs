@@ -0,0 +1,314 @@
+/// Log of every answer submitted to adventofcode.com, stored in `submissions.json` in the data directory of a year.
+/// Used to avoid resubmitting answers that are already known to be wrong.
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{aoc_client::SubmissionOutcome, Day, Year};
+
+static SUBMISSIONS_FILE_NAME: &str = "submissions.json";
+
+/// A single submitted answer together with the server's verdict.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub outcome: SubmissionOutcome,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+/// Whether an answer should be submitted, given earlier submissions of the same part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubmissionCheck {
+    Ok,
+    /// The answer can be submitted, but is probably wrong.
+    Warn(String),
+    /// The answer is known to be wrong.
+    Refuse(String),
+}
+
+impl Submission {
+    /// Creates a submission with the current time as timestamp.
+    pub fn now(day: Day, part: u8, answer: &str, outcome: SubmissionOutcome) -> Self {
+        Submission {
+            day,
+            part,
+            answer: answer.to_string(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            outcome,
+        }
+    }
+}
+
+impl Submissions {
+    /// Dehydrate submissions of `year` to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(year.data_dir().join(SUBMISSIONS_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate submissions of `year` from a JSON file. If not present, returns an empty log.
+    /// A log that can't be read is an error, so it isn't overwritten by [`Submissions::store_file`].
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        let path = year.data_dir().join(SUBMISSIONS_FILE_NAME);
+        match fs::read_to_string(&path) {
+            Ok(s) => Submissions::from_str(&s)
+                .map_err(|e| format!("Invalid submissions file {}: {e}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Submissions::default()),
+            Err(e) => Err(format!("Could not read {}: {e}", path.display())),
+        }
+    }
+
+    /// Checks `answer` against earlier submissions of the same part.
+    /// Refuses answers that were already rejected and warns about answers outside of the bounds given by
+    /// earlier "too high" and "too low" responses.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> SubmissionCheck {
+        let previous: Vec<&Submission> = self
+            .data
+            .iter()
+            .filter(|s| s.day == day && s.part == part)
+            .collect();
+
+        if let Some(known) = previous.iter().find(|s| {
+            s.answer == answer
+                && matches!(
+                    s.outcome,
+                    SubmissionOutcome::TooHigh
+                        | SubmissionOutcome::TooLow
+                        | SubmissionOutcome::Incorrect
+                )
+        }) {
+            return SubmissionCheck::Refuse(format!(
+                "{answer} was already submitted and is {}.",
+                known.outcome
+            ));
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return SubmissionCheck::Ok;
+        };
+
+        let bound = |outcome: SubmissionOutcome| {
+            previous
+                .iter()
+                .filter(move |s| s.outcome == outcome)
+                .filter_map(|s| s.answer.parse::<i128>().ok())
+        };
+
+        if let Some(too_high) = bound(SubmissionOutcome::TooHigh).min() {
+            if value >= too_high {
+                return SubmissionCheck::Warn(format!(
+                    "{answer} is not lower than {too_high}, which was too high."
+                ));
+            }
+        }
+
+        if let Some(too_low) = bound(SubmissionOutcome::TooLow).max() {
+            if value <= too_low {
+                return SubmissionCheck::Warn(format!(
+                    "{answer} is not higher than {too_low}, which was too low."
+                ));
+            }
+        }
+
+        SubmissionCheck::Ok
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submissions> for JsonValue {
+    fn from(value: &Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+        JsonValue::Object(map)
+    }
+}
+
+impl FromStr for Submissions {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("not valid JSON file."))?;
+
+        let data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "outcome".into(),
+            JsonValue::String(value.outcome.to_string()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .filter(|x| **x == 1.0 || **x == 2.0)
+            .ok_or("Expected submission.part to be 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        let outcome = json
+            .get("outcome")
+            .and_then(|v| v.get::<String>())
+            .and_then(|x| x.parse().ok())
+            .ok_or("Expected submission.outcome to be a submission outcome.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(Submission {
+            day,
+            part: *part as u8,
+            answer: answer.clone(),
+            timestamp: *timestamp as u64,
+            outcome,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Submission, SubmissionCheck, Submissions};
+    use crate::{day, template::aoc_client::SubmissionOutcome};
+
+    fn submission(part: u8, answer: &str, outcome: SubmissionOutcome) -> Submission {
+        Submission {
+            day: day!(1),
+            part,
+            answer: answer.into(),
+            timestamp: 1_701_406_800,
+            outcome,
+        }
+    }
+
+    fn get_mock_submissions() -> Submissions {
+        Submissions {
+            data: vec![
+                submission(1, "100", SubmissionOutcome::TooHigh),
+                submission(1, "20", SubmissionOutcome::TooLow),
+                submission(1, "50", SubmissionOutcome::Incorrect),
+                submission(2, "abc", SubmissionOutcome::Wait),
+            ],
+        }
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let submissions = get_mock_submissions();
+        assert!(matches!(
+            submissions.check(day!(1), 1, "50"),
+            SubmissionCheck::Refuse(_)
+        ));
+        assert!(matches!(
+            submissions.check(day!(1), 1, "100"),
+            SubmissionCheck::Refuse(_)
+        ));
+    }
+
+    #[test]
+    fn warns_about_contradicting_answers() {
+        let submissions = get_mock_submissions();
+        assert!(matches!(
+            submissions.check(day!(1), 1, "120"),
+            SubmissionCheck::Warn(_)
+        ));
+        assert!(matches!(
+            submissions.check(day!(1), 1, "-3"),
+            SubmissionCheck::Warn(_)
+        ));
+        assert_eq!(submissions.check(day!(1), 1, "42"), SubmissionCheck::Ok);
+    }
+
+    #[test]
+    fn scopes_checks_to_parts() {
+        let submissions = get_mock_submissions();
+        assert_eq!(submissions.check(day!(1), 2, "120"), SubmissionCheck::Ok);
+        assert_eq!(submissions.check(day!(2), 1, "50"), SubmissionCheck::Ok);
+        // answers that were not judged can be resubmitted.
+        assert_eq!(submissions.check(day!(1), 2, "abc"), SubmissionCheck::Ok);
+    }
+
+    #[test]
+    fn roundtrips_submissions() {
+        let submissions = get_mock_submissions();
+        let json = tinyjson::JsonValue::from(&submissions).stringify().unwrap();
+        let parsed: Submissions = json.parse().unwrap();
+        assert_eq!(parsed.data, submissions.data);
+    }
+
+    #[test]
+    fn rejects_invalid_outcomes() {
+        let json = r#"{ "data": [{ "day": "01", "part": 1, "answer": "1", "timestamp": 0, "outcome": "maybe" }] }"#;
+        assert!(json.parse::<Submissions>().is_err());
+    }
+}
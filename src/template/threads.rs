@@ -0,0 +1,55 @@
+/// Control over rayon's global thread pool, which the parallel solutions use.
+use std::{env, num::NonZeroUsize, thread};
+
+/// Reads the `--threads <n>` flag from the process arguments.
+pub fn from_args() -> Option<usize> {
+    let args: Vec<String> = env::args().collect();
+
+    args.iter()
+        .position(|x| x == "--threads")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|x| x.parse().ok())
+        .filter(|x| *x > 0)
+}
+
+/// Sizes rayon's global thread pool according to `--threads`. Needs to run before any solution uses rayon.
+pub fn configure_from_args() {
+    if let Some(threads) = from_args() {
+        if let Err(e) = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()
+        {
+            eprintln!("failed to configure thread pool: {e}");
+        }
+    }
+}
+
+/// Number of threads the machine can run in parallel.
+pub fn available() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+/// Thread counts to bench when scaling: powers of two up to `max`, followed by `max` itself.
+pub fn ladder(max: usize) -> Vec<usize> {
+    let mut ladder: Vec<usize> = (0..usize::BITS)
+        .map(|x| 1 << x)
+        .take_while(|x| *x < max)
+        .collect();
+    ladder.push(max.max(1));
+    ladder
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::ladder;
+
+    #[test]
+    fn builds_thread_ladder() {
+        assert_eq!(ladder(1), [1]);
+        assert_eq!(ladder(2), [1, 2]);
+        assert_eq!(ladder(8), [1, 2, 4, 8]);
+        assert_eq!(ladder(12), [1, 2, 4, 8, 12]);
+    }
+}
@@ -1,17 +1,46 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io::Error, process::Command, str::FromStr, time::Duration};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
-
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+use crate::template::{
+    alloc::AllocStats, perf::Counters, record::Failure, stats::Stats, Day, Year,
+};
+
+static TIMINGS_FILE_NAME: &str = "timings.json";
+
+/// Version of the `timings.json` schema written by [`Timings::store_file`].
+/// Files without a `version` key are version 1, which stored parts as display strings like `"74.13ms"`.
+pub const SCHEMA_VERSION: u8 = 2;
+
+/// Represents benchmark times for a single part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartTiming {
+    /// Mean duration in nanoseconds.
+    pub nanos: f64,
+    /// Number of samples the mean was taken over. Unknown for timings migrated from version 1.
+    pub samples: Option<u128>,
+    /// Sample distribution, if it was recorded.
+    pub stats: Option<Stats>,
+    /// Heap usage of a single run, if it was recorded with `cargo time --alloc`.
+    pub alloc: Option<AllocStats>,
+    /// Hardware counters per run, if they were collected with `cargo time --counters`.
+    pub counters: Option<Counters>,
+    /// Set if the part failed or was stopped by a limit, `nanos` then holds the time until then.
+    pub failure: Option<Failure>,
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    /// Time spent in the parse step, for solutions that declare one. Included in `total_nanos`.
+    pub parse: Option<PartTiming>,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
     pub total_nanos: f64,
+    /// Commit the timing was measured on, with a `-dirty` suffix for uncommitted changes.
+    pub commit: Option<String>,
+    /// Output of `rustc -V` for the toolchain the timing was measured with.
+    pub toolchain: Option<String>,
 }
 
 /// Represents benchmark times for a set of days.
@@ -21,17 +50,52 @@ pub struct Timings {
     pub data: Vec<Timing>,
 }
 
+impl PartTiming {
+    pub fn duration(&self) -> Duration {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Duration::from_nanos(self.nanos.round() as u64)
+    }
+}
+
+impl From<Duration> for PartTiming {
+    fn from(value: Duration) -> Self {
+        #[allow(clippy::cast_precision_loss)]
+        PartTiming {
+            nanos: value.as_nanos() as f64,
+            samples: None,
+            stats: None,
+            alloc: None,
+            counters: None,
+            failure: None,
+        }
+    }
+}
+
+impl From<Stats> for PartTiming {
+    fn from(value: Stats) -> Self {
+        #[allow(clippy::cast_precision_loss)]
+        PartTiming {
+            nanos: value.mean.as_nanos() as f64,
+            samples: Some(value.samples),
+            stats: Some(value),
+            alloc: None,
+            counters: None,
+            failure: None,
+        }
+    }
+}
+
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Dehydrate timings of `year` to a JSON file in its data directory.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(year.data_dir().join(TIMINGS_FILE_NAME))?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
+    /// Rehydrate timings of `year` from a JSON file. If not present, returns empty timings.
+    pub fn read_from_file(year: Year) -> Self {
+        let s = fs::read_to_string(year.data_dir().join(TIMINGS_FILE_NAME))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from);
 
@@ -58,7 +122,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|t| t.day);
         Timings { data }
     }
 
@@ -67,19 +131,78 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
+    /// Whether both parts of `day` were benched. Parts that failed are benched again.
     pub fn is_day_complete(&self, day: Day) -> bool {
-        self.data
-            .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+        self.data.iter().any(|t| {
+            let is_complete =
+                |part: &Option<PartTiming>| part.as_ref().is_some_and(|p| p.failure.is_none());
+            t.day == day && is_complete(&t.part_1) && is_complete(&t.part_2)
+        })
     }
 }
 
+/// Returns the short hash of the checked out commit, if run inside a git repository.
+pub fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    let commit = String::from_utf8(output.stdout).ok()?.trim().to_string();
+
+    let is_dirty = Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .output()
+        .is_ok_and(|o| !o.stdout.is_empty());
+
+    Some(if is_dirty {
+        format!("{commit}-dirty")
+    } else {
+        commit
+    })
+}
+
+/// Returns the version string of the active rust toolchain, e.g. `rustc 1.74.0 (79e9716c9 2023-11-13)`.
+pub fn current_toolchain() -> Option<String> {
+    let output = Command::new("rustc")
+        .arg("-V")
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    Some(String::from_utf8(output.stdout).ok()?.trim().to_string())
+}
+
+/// Parses a duration formatted with `{:?}`, e.g. `74.13ms`.
+pub fn parse_duration(s: &str) -> Option<Duration> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    let s = s.trim();
+    let (value, factor) = if let Some(x) = s.strip_suffix("ns") {
+        (x, 1_f64)
+    } else if let Some(x) = s.strip_suffix("µs") {
+        (x, 1_000_f64)
+    } else if let Some(x) = s.strip_suffix("ms") {
+        (x, 1_000_000_f64)
+    } else {
+        (s.strip_suffix('s')?, 1_000_000_000_f64)
+    };
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    value
+        .parse::<f64>()
+        .ok()
+        .map(|x| Duration::from_nanos((x * factor).round() as u64))
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert(
+            "version".into(),
+            JsonValue::Number(f64::from(SCHEMA_VERSION)),
+        );
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
@@ -95,49 +218,174 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let document = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        let version = match document.get("version") {
+            None => 1.0,
+            Some(v) => v
+                .get::<f64>()
+                .copied()
+                .ok_or("expected `json.version` to be a number.")?,
+        };
+
+        let json_data = document
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
             .ok_or("expected `json.data` to be an array.")?;
 
+        #[allow(clippy::float_cmp)]
+        let parse: fn(&JsonValue) -> Result<Timing, String> = if version == 1.0 {
+            Timing::try_from_v1
+        } else if version == f64::from(SCHEMA_VERSION) {
+            |v| Timing::try_from(v)
+        } else {
+            return Err(format!(
+                "unsupported timings version {version}, expected at most {SCHEMA_VERSION}."
+            ));
+        };
+
         Ok(Timings {
-            data: json_data
-                .iter()
-                .map(Timing::try_from)
-                .collect::<Result<_, _>>()?,
+            data: json_data.iter().map(parse).collect::<Result<_, _>>()?,
         })
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
+fn optional<T>(value: Option<T>, f: impl Fn(T) -> JsonValue) -> JsonValue {
+    match value {
+        Some(x) => f(x),
+        None => JsonValue::Null,
+    }
+}
+
+impl From<&PartTiming> for JsonValue {
+    fn from(value: &PartTiming) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "samples".into(),
+            optional(value.samples, |x| JsonValue::Number(x as f64)),
+        );
+        map.insert(
+            "stats".into(),
+            optional(value.stats.as_ref(), JsonValue::from),
+        );
+        map.insert(
+            "alloc".into(),
+            optional(value.alloc.as_ref(), JsonValue::from),
+        );
+        map.insert(
+            "counters".into(),
+            optional(value.counters.as_ref(), JsonValue::from),
+        );
+        match &value.failure {
+            Some(failure) => failure.insert_into(&mut map),
+            None => {
+                map.insert("failure".into(), JsonValue::Null);
+            }
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartTiming {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part timing to be a JSON object.")?;
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected part.nanos to be a number.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = json
+            .get("samples")
+            .map(|v| {
+                if v.is_null() {
+                    Ok(None)
+                } else {
+                    v.get::<f64>().map(|x| Some(*x as u128)).ok_or(())
+                }
+            })
+            .unwrap_or(Ok(None))
+            .or(Err("Expected part.samples to be null or a number."))?;
+
+        let stats = match json.get("stats") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                Stats::try_from(v)
+                    .map_err(|e| format!("Expected part.stats to be null or stats: {e}"))?,
+            ),
+        };
+
+        let alloc =
+            match json.get("alloc") {
+                None => None,
+                Some(v) if v.is_null() => None,
+                Some(v) => Some(AllocStats::try_from(v).map_err(|e| {
+                    format!("Expected part.alloc to be null or allocation stats: {e}")
+                })?),
+            };
+
+        let counters = match json.get("counters") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                Counters::try_from(v)
+                    .map_err(|e| format!("Expected part.counters to be null or counters: {e}"))?,
+            ),
+        };
+
+        let failure = Failure::read_from(json).map_err(|e| format!("Invalid part: {e}"))?;
+
+        Ok(PartTiming {
+            nanos,
+            samples,
+            stats,
+            alloc,
+            counters,
+            failure,
+        })
+    }
+}
+
 impl From<&Timing> for JsonValue {
     fn from(value: &Timing) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
-
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
-
+        map.insert(
+            "parse".into(),
+            optional(value.parse.as_ref(), JsonValue::from),
+        );
         map.insert(
             "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            optional(value.part_1.as_ref(), JsonValue::from),
         );
-
         map.insert(
             "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            optional(value.part_2.as_ref(), JsonValue::from),
+        );
+        map.insert(
+            "commit".into(),
+            optional(value.commit.clone(), JsonValue::String),
+        );
+        map.insert(
+            "toolchain".into(),
+            optional(value.toolchain.clone(), JsonValue::String),
         );
 
         JsonValue::Object(map)
@@ -158,15 +406,68 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+        let part = |key: &str| match json.get(key) {
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => PartTiming::try_from(v).map(Some),
+            None => Err(format!(
+                "Expected timing.{key} to be null or a part timing."
+            )),
+        };
+
+        let string = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => v
+                .get::<String>()
+                .cloned()
+                .map(Some)
+                .ok_or(format!("Expected timing.{key} to be null or string.")),
+        };
+
+        let total_nanos = json
+            .get("total_nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected timing.total_nanos to be a number.")?;
+
+        Ok(Timing {
+            day,
+            parse: part("parse")?,
+            part_1: part("part_1")?,
+            part_2: part("part_2")?,
+            total_nanos,
+            commit: string("commit")?,
+            toolchain: string("toolchain")?,
+        })
+    }
+}
 
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+impl Timing {
+    /// Reads a timing stored with schema version 1, where parts were display strings.
+    fn try_from_v1(value: &JsonValue) -> Result<Self, String> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected timing.day to be a Day struct.")?;
+
+        let part = |key: &str| -> Result<Option<PartTiming>, String> {
+            let display = json
+                .get(key)
+                .map(|v| if v.is_null() { None } else { v.get::<String>() })
+                .ok_or(format!("Expected timing.{key} to be null or string."))?;
+
+            display
+                .map(|display| {
+                    parse_duration(display)
+                        .map(PartTiming::from)
+                        .ok_or(format!("Expected timing.{key} to be a duration."))
+                })
+                .transpose()
+        };
 
         let total_nanos = json
             .get("total_nanos")
@@ -175,9 +476,12 @@ impl TryFrom<&JsonValue> for Timing {
 
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            parse: None,
+            part_1: part("part_1")?,
+            part_2: part("part_2")?,
             total_nanos,
+            commit: None,
+            toolchain: None,
         })
     }
 }
@@ -186,6 +490,8 @@ impl TryFrom<&JsonValue> for Timing {
 
 #[cfg(feature = "test_lib")]
 mod tests {
+    use std::time::Duration;
+
     use crate::day;
 
     use super::{Timing, Timings};
@@ -195,39 +501,119 @@ mod tests {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    parse: None,
+                    part_1: Some(Duration::from_millis(10).into()),
+                    part_2: Some(Duration::from_millis(20).into()),
                     total_nanos: 3e+10,
+                    commit: None,
+                    toolchain: None,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    parse: None,
+                    part_1: Some(Duration::from_millis(30).into()),
+                    part_2: Some(Duration::from_millis(40).into()),
                     total_nanos: 7e+10,
+                    commit: None,
+                    toolchain: None,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
+                    parse: None,
+                    part_1: Some(Duration::from_millis(40).into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    commit: None,
+                    toolchain: None,
                 },
             ],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use std::time::Duration;
+
+        use crate::{
+            day,
+            template::{record::Failure, timings::Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "parse": null, "part_1": { "nanos": 1000000, "samples": 10, "stats": null }, "part_2": null, "total_nanos": 1000000, "commit": "abc1234", "toolchain": null }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(timing.parse, None);
+            let part_1 = timing.part_1.as_ref().unwrap();
+            assert_eq!(part_1.duration(), Duration::from_millis(1));
+            assert_eq!(part_1.samples, Some(10));
+            assert_eq!(part_1.stats, None);
+            assert_eq!(timing.part_2, None);
+            assert_eq!(timing.total_nanos, 1_000_000_f64);
+            assert_eq!(timing.commit.as_deref(), Some("abc1234"));
+            assert_eq!(timing.toolchain, None);
+        }
+
+        #[test]
+        fn handles_json_stats() {
+            let json = r#"{ "version": 2, "data": [{ "day": "06", "parse": null, "part_1": { "nanos": 1000, "samples": 3, "stats": { "samples": 3, "duration_nanos": 1000, "min_nanos": 900, "median_nanos": 950, "p95_nanos": 1150, "p99_nanos": 1150, "std_dev_nanos": 108, "outliers": 0 } }, "part_2": null, "total_nanos": 1000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1.as_ref().unwrap().stats.unwrap();
+            assert_eq!(stats.samples, 3);
+            assert_eq!(stats.median, Duration::from_nanos(950));
+        }
+
+        #[test]
+        fn handles_parse_step() {
+            let json = r#"{ "version": 2, "data": [{ "day": "17", "parse": { "nanos": 500, "samples": 1, "stats": null }, "part_1": { "nanos": 1000, "samples": 1, "stats": null }, "part_2": null, "total_nanos": 1500 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.parse.as_ref().unwrap().nanos, 500_f64);
+            assert_eq!(timing.total_nanos, 1500_f64);
+        }
+
+        #[test]
+        fn handles_failures() {
+            let json = r#"{ "version": 2, "data": [{ "day": "21", "parse": null, "part_1": null, "part_2": { "nanos": 1000, "samples": 1, "stats": null, "failure": "oom" }, "total_nanos": 0 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let part_2 = timing.part_2.as_ref().unwrap();
+            assert_eq!(part_2.failure, Some(Failure::OutOfMemory));
+
+            let json = r#"{ "version": 2, "data": [{ "day": "10", "parse": null, "part_1": { "nanos": 20, "samples": 1, "stats": null, "failure": "panic", "failure_message": "valid input" }, "part_2": null, "total_nanos": 0 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let part_1 = timings.data[0].part_1.as_ref().unwrap();
+            assert_eq!(
+                part_1.failure,
+                Some(Failure::Panicked("valid input".into()))
+            );
+        }
+
+        #[test]
+        fn migrates_version_1_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "74.13ms", "part_2": null, "total_nanos": 74130000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let part_1 = timing.part_1.as_ref().unwrap();
+            assert_eq!(part_1.nanos, 74_130_000_f64);
+            assert_eq!(part_1.samples, None);
             assert_eq!(timing.part_2, None);
-            assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.commit, None);
+        }
+
+        #[test]
+        fn roundtrips_timings() {
+            let timings = super::get_mock_timings();
+            let json = tinyjson::JsonValue::from(timings.clone())
+                .stringify()
+                .unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data.len(), timings.data.len());
+            assert_eq!(parsed.data[2].part_1, timings.data[2].part_1);
+            assert_eq!(parsed.data[2].part_2, None);
         }
 
         #[test]
@@ -237,6 +623,13 @@ mod tests {
             assert_eq!(timings.data.len(), 0);
         }
 
+        #[test]
+        #[should_panic]
+        fn panics_for_unknown_versions() {
+            let json = r#"{ "version": 3, "data": [] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
+
         #[test]
         #[should_panic]
         fn panics_for_invalid_json() {
@@ -252,8 +645,32 @@ mod tests {
         }
     }
 
+    mod parse_duration {
+        use std::time::Duration;
+
+        use crate::template::timings::parse_duration;
+
+        #[test]
+        fn parses_all_units() {
+            assert_eq!(parse_duration("74.13ns"), Some(Duration::from_nanos(74)));
+            assert_eq!(parse_duration("2.5µs"), Some(Duration::from_nanos(2500)));
+            assert_eq!(
+                parse_duration("74.13ms"),
+                Some(Duration::from_nanos(74_130_000))
+            );
+            assert_eq!(parse_duration("2s"), Some(Duration::from_secs(2)));
+        }
+
+        #[test]
+        fn rejects_invalid_durations() {
+            assert_eq!(parse_duration("-"), None);
+            assert_eq!(parse_duration("fast"), None);
+        }
+    }
+
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::timings::SCHEMA_VERSION;
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
@@ -273,12 +690,30 @@ mod tests {
                 3
             );
         }
+
+        #[test]
+        fn writes_schema_version() {
+            let value = JsonValue::from(get_mock_timings());
+            let version = value
+                .get::<HashMap<String, JsonValue>>()
+                .unwrap()
+                .get("version")
+                .unwrap()
+                .get::<f64>()
+                .copied();
+            assert_eq!(version, Some(f64::from(SCHEMA_VERSION)));
+        }
     }
 
     mod is_day_complete {
+        use std::time::Duration;
+
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                record::Failure,
+                timings::{PartTiming, Timing, Timings},
+            },
         };
 
         #[test]
@@ -286,13 +721,16 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    parse: None,
+                    part_1: Some(Duration::from_millis(1).into()),
+                    part_2: Some(Duration::from_millis(2).into()),
                     total_nanos: 3_000_000_000_f64,
+                    commit: None,
+                    toolchain: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -300,13 +738,36 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
+                    parse: None,
+                    part_1: Some(Duration::from_millis(1).into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    commit: None,
+                    toolchain: None,
+                }],
+            };
+
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+
+        #[test]
+        fn handles_failed_parts() {
+            let timings = Timings {
+                data: vec![Timing {
+                    day: day!(21),
+                    parse: None,
+                    part_1: Some(Duration::from_millis(1).into()),
+                    part_2: Some(PartTiming {
+                        failure: Some(Failure::TimedOut),
+                        ..Duration::from_secs(10).into()
+                    }),
+                    total_nanos: 1_000_000_f64,
+                    commit: None,
+                    toolchain: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(21)), false);
         }
 
         #[test]
@@ -314,13 +775,16 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    commit: None,
+                    toolchain: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -338,9 +802,12 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    commit: None,
+                    toolchain: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -358,9 +825,12 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    commit: None,
+                    toolchain: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -0,0 +1,374 @@
+/// Alternative implementations of parts, see [`Variant`].
+///
+/// `--variant <name>` makes a solution process run the variants called `name` instead of their parts.
+/// `--variants` runs every variant next to its part, which `cargo time --variants` uses to bench them side by side
+/// and to check that they agree with the part, see [`run_variants`].
+use std::{env, time::Duration};
+
+use crate::template::{
+    all_days,
+    record::{Failure, PartRecord},
+    run_multi::{child_commands, RunOptions},
+    runner::step_label,
+    solution::Metadata,
+    Day, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+
+pub use crate::template::solution::Variant;
+
+/// Which implementations of the parts a solution process runs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Selection {
+    /// Only the parts, the default.
+    Parts,
+    /// The variants called `name` instead of their parts, with `--variant <name>`.
+    Named(String),
+    /// Every part that has variants followed by its variants, with `--variants`.
+    All,
+}
+
+impl Selection {
+    /// Reads the `--variant <name>` and `--variants` flags from the process arguments.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        if let Some(name) = args
+            .iter()
+            .position(|x| x == "--variant")
+            .and_then(|i| args.get(i + 1))
+        {
+            Selection::Named(name.clone())
+        } else if args.iter().any(|x| x == "--variants") {
+            Selection::All
+        } else {
+            Selection::Parts
+        }
+    }
+}
+
+/// The parts and variants a solution runs for `selection`, in order. Variants are `Some` with their name.
+pub fn steps(
+    day: Day,
+    metadata: &Metadata,
+    selection: &Selection,
+) -> Result<Vec<(u8, Option<&'static str>)>, String> {
+    match selection {
+        Selection::Parts => Ok(metadata.parts.iter().map(|&part| (part, None)).collect()),
+        Selection::Named(name) => {
+            let mut variants = metadata.variants_named(name);
+            if variants.is_empty() {
+                let mut names: Vec<&str> = vec![];
+                for variant in metadata.variants {
+                    if !names.contains(&variant.name) {
+                        names.push(variant.name);
+                    }
+                }
+                return Err(if names.is_empty() {
+                    format!("Day {day} has no variants.")
+                } else {
+                    format!(
+                        "Day {day} has no variant `{name}`, expecting one of: {}.",
+                        names.join(", ")
+                    )
+                });
+            }
+
+            variants.sort_by_key(|variant| variant.part);
+            Ok(variants
+                .into_iter()
+                .map(|variant| (variant.part, Some(variant.name)))
+                .collect())
+        }
+        Selection::All => Ok(metadata
+            .parts
+            .iter()
+            .flat_map(|&part| {
+                let variants: Vec<(u8, Option<&'static str>)> = metadata
+                    .variants
+                    .iter()
+                    .filter(|variant| variant.part == part)
+                    .map(|variant| (part, Some(variant.name)))
+                    .collect();
+
+                // NOTE: parts without variants have nothing to be compared with.
+                if variants.is_empty() {
+                    variants
+                } else {
+                    [(part, None)].into_iter().chain(variants).collect()
+                }
+            })
+            .collect()),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A part or one of its variants, compared with the part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComparedStep {
+    pub part: u8,
+    /// Name of the variant, `None` for the part itself.
+    pub variant: Option<String>,
+    pub answer: Option<String>,
+    pub mean: Duration,
+    pub failure: Option<Failure>,
+    /// Time of the part divided by the time of this step, `None` if either of them failed.
+    pub speedup: Option<f64>,
+    /// Whether the step returned the same answer as the part, and both of them returned one.
+    /// Variants that returned `None` skipped the input, e.g. because it is too large for them, and count as agreeing.
+    pub agrees: bool,
+}
+
+/// Compares every variant of `day` with its part, ordered like the records.
+/// Records of parts that have no variants, and of the parse step, are left out.
+pub fn compare_steps(records: &[PartRecord], day: Day) -> Vec<ComparedStep> {
+    let records: Vec<&PartRecord> = records
+        .iter()
+        .filter(|r| r.day == day && r.input.is_none())
+        .collect();
+
+    let reference = |part: u8| {
+        records
+            .iter()
+            .find(|r| r.part == part && r.variant.is_none())
+    };
+
+    records
+        .iter()
+        .filter(|r| {
+            records
+                .iter()
+                .any(|x| x.part == r.part && x.variant.is_some())
+        })
+        .map(|record| {
+            let reference = reference(record.part).filter(|r| r.failure.is_none());
+            let is_ok = record.failure.is_none();
+            let is_skipped = is_ok && record.variant.is_some() && record.answer.is_none();
+
+            ComparedStep {
+                part: record.part,
+                variant: record.variant.clone(),
+                answer: record.answer.clone(),
+                mean: record.stats.mean,
+                failure: record.failure.clone(),
+                speedup: reference.filter(|_| is_ok && !is_skipped).map(|reference| {
+                    reference.stats.mean.as_secs_f64() / record.stats.mean.as_secs_f64().max(1e-12)
+                }),
+                agrees: is_skipped
+                    || is_ok
+                        && record.answer.is_some()
+                        && reference.is_some_and(|reference| reference.answer == record.answer),
+            }
+        })
+        .collect()
+}
+
+/// Benches every variant of `days` of `year` next to its part and prints them side by side.
+/// Days without variants are skipped. Returns whether every variant agrees with its part.
+pub fn run_variants(year: Year, days: &[Day], options: &RunOptions) -> bool {
+    let days: Vec<Day> = all_days().filter(|day| days.contains(day)).collect();
+
+    let options = RunOptions {
+        is_release: true,
+        is_timed: true,
+        is_variants: true,
+        ..*options
+    };
+
+    let output = match child_commands::run_solutions(year, &days, &options, |_| {}) {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("Failed to run solutions: {e}");
+            return false;
+        }
+    };
+
+    let mut all_agree = true;
+    let mut need_space = false;
+
+    for day in days {
+        let steps = compare_steps(&output.records, day);
+        if steps.is_empty() {
+            continue;
+        }
+
+        if need_space {
+            println!();
+        }
+        need_space = true;
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+        all_agree &= print_steps(&steps);
+    }
+
+    if !need_space {
+        println!("No variants to compare.");
+    }
+
+    all_agree
+}
+
+/// Prints a table of `steps` and the variants that don't agree with their part. Returns whether all of them agree.
+fn print_steps(steps: &[ComparedStep]) -> bool {
+    let labels: Vec<String> = steps
+        .iter()
+        .map(|step| step_label(step.part, step.variant.as_deref()))
+        .collect();
+    let width = labels
+        .iter()
+        .map(String::len)
+        .chain(["Implementation".len()])
+        .max()
+        .unwrap_or(0)
+        + 2;
+
+    println!("{:width$}Time         Speedup  Answer", "Implementation");
+
+    let mut disagreements = vec![];
+
+    for (step, label) in steps.iter().zip(&labels) {
+        let time = format!("{:.1?}", step.mean);
+        let speedup = match (&step.variant, step.speedup) {
+            (Some(_), Some(speedup)) => format!("{speedup:.2}×"),
+            _ => String::new(),
+        };
+        let answer = match (&step.failure, &step.answer) {
+            (Some(failure), _) => format!("✖ {failure}"),
+            // NOTE: multi-line answers only show their first line, which is enough to tell them apart.
+            (None, Some(answer)) => answer.lines().next().unwrap_or_default().to_string(),
+            (None, None) if step.variant.is_some() => "skipped".into(),
+            (None, None) => "✖".into(),
+        };
+
+        println!("{label:width$}{time:<13}{speedup:<9}{answer}");
+
+        if step.variant.is_some() && !step.agrees {
+            disagreements.push(label.as_str());
+        }
+    }
+
+    if disagreements.is_empty() {
+        println!("{ANSI_ITALIC}All variants agree.{ANSI_RESET}");
+    } else {
+        println!(
+            "{ANSI_BOLD}✖ Not agreeing with their part:{ANSI_RESET} {}",
+            disagreements.join(", ")
+        );
+    }
+
+    disagreements.is_empty()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{compare_steps, steps, Selection, Variant};
+    use crate::{
+        day,
+        template::{
+            record::{Failure, PartRecord},
+            solution::Metadata,
+            stats::Stats,
+        },
+    };
+
+    const METADATA: Metadata = Metadata {
+        parts: &[1, 2],
+        source: file!(),
+        has_parse: false,
+        variants: &[
+            Variant {
+                name: "generic",
+                part: 2,
+            },
+            Variant {
+                name: "generic",
+                part: 1,
+            },
+            Variant {
+                name: "crt",
+                part: 2,
+            },
+        ],
+    };
+
+    #[test]
+    fn selects_steps() {
+        assert_eq!(
+            steps(day!(8), &METADATA, &Selection::Parts),
+            Ok(vec![(1, None), (2, None)])
+        );
+        assert_eq!(
+            steps(day!(8), &METADATA, &Selection::Named("generic".into())),
+            Ok(vec![(1, Some("generic")), (2, Some("generic"))])
+        );
+        assert_eq!(
+            steps(day!(8), &METADATA, &Selection::All),
+            Ok(vec![
+                (1, None),
+                (1, Some("generic")),
+                (2, None),
+                (2, Some("generic")),
+                (2, Some("crt")),
+            ])
+        );
+
+        let error = steps(day!(8), &METADATA, &Selection::Named("brute".into())).unwrap_err();
+        assert_eq!(
+            error,
+            "Day 08 has no variant `brute`, expecting one of: generic, crt."
+        );
+    }
+
+    fn record(part: u8, variant: Option<&str>, answer: Option<&str>, micros: u64) -> PartRecord {
+        PartRecord {
+            day: day!(8),
+            input: None,
+            part,
+            variant: variant.map(Into::into),
+            answer: answer.map(Into::into),
+            stats: Stats::single(Duration::from_micros(micros)),
+            alloc: None,
+            counters: None,
+            failure: None,
+        }
+    }
+
+    #[test]
+    fn compares_variants_with_their_part() {
+        let records = [
+            record(0, None, None, 5),
+            record(1, None, Some("2"), 10),
+            record(2, None, Some("6"), 40),
+            record(2, Some("crt"), Some("6"), 20),
+            record(2, Some("brute"), Some("7"), 80),
+            record(2, Some("bounded"), None, 3),
+            PartRecord {
+                failure: Some(Failure::Panicked("overflow".into())),
+                ..record(2, Some("naive"), None, 1)
+            },
+        ];
+
+        let steps = compare_steps(&records, day!(8));
+        let summary: Vec<(Option<&str>, bool, Option<f64>)> = steps
+            .iter()
+            .map(|step| (step.variant.as_deref(), step.agrees, step.speedup))
+            .collect();
+
+        assert_eq!(
+            summary,
+            [
+                (None, true, Some(1.0)),
+                (Some("crt"), true, Some(2.0)),
+                (Some("brute"), false, Some(0.5)),
+                (Some("bounded"), true, None),
+                (Some("naive"), false, None),
+            ]
+        );
+        assert!(compare_steps(&records, day!(9)).is_empty());
+    }
+}
@@ -0,0 +1,165 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::{env, fmt};
+
+use crate::template::Day;
+
+/// An Advent of Code event, i.e. a year starting at 2015.
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2023).unwrap();
+/// assert_eq!(year.to_string(), "2023")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// The year of solutions that do not declare one, configured with `AOC_YEAR` in `.cargo/config.toml`.
+    /// Its data lives directly in `data/` and its solutions in `src/bin/<day>.rs`.
+    pub const PRIMARY: Year = parse_primary(env!("AOC_YEAR"));
+
+    /// Creates a [`Year`] from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if !(2015..=9999).contains(&year) {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub const fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    pub fn is_primary(self) -> bool {
+        self == Year::PRIMARY
+    }
+
+    /// Directory holding the inputs, examples, answers and timings of this year.
+    /// The primary year uses `data/` itself, other years use `data/<year>/`.
+    pub fn data_dir(self) -> PathBuf {
+        let data = env::current_dir().unwrap().join("data");
+        if self.is_primary() {
+            data
+        } else {
+            data.join(self.to_string())
+        }
+    }
+
+    /// Name of the binary holding the solution for `day` of this year, e.g. `01` or `2022-01`.
+    pub fn bin_name(self, day: Day) -> String {
+        if self.is_primary() {
+            day.to_string()
+        } else {
+            format!("{self}-{day}")
+        }
+    }
+}
+
+const fn parse_primary(s: &str) -> Year {
+    let bytes = s.as_bytes();
+    let mut year: u16 = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        assert!(
+            bytes[i].is_ascii_digit() && i < 4,
+            "AOC_YEAR must be a four digit year"
+        );
+        year = year * 10 + (bytes[i] - b'0') as u16;
+        i += 1;
+    }
+
+    assert!(year >= 2015, "AOC_YEAR must be 2015 or later");
+    Year(year)
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}", self.0)
+    }
+}
+
+impl From<Day> for (Year, Day) {
+    fn from(day: Day) -> Self {
+        (Year::PRIMARY, day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("expecting a year of 2015 or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015 && $year <= 9999,
+            concat!(
+                "invalid year `",
+                $year,
+                "`, expecting a year of 2015 or later"
+            ),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_primary, Year};
+    use crate::{day, year};
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2022".parse::<Year>().unwrap(), year!(2022));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("22".parse::<Year>().is_err());
+        assert_eq!(parse_primary("2023"), year!(2023));
+    }
+
+    #[test]
+    fn names_binaries() {
+        assert_eq!(Year::PRIMARY.bin_name(day!(3)), "03");
+        let other = Year::new(Year::PRIMARY.into_inner() + 1).unwrap();
+        assert_eq!(other.bin_name(day!(3)), format!("{other}-03"));
+        assert_eq!(other.data_dir().ends_with(other.to_string()), true);
+    }
+}